@@ -0,0 +1,51 @@
+//! Times booleans against building the BSP tree of the larger operand. Run with
+//! `cargo bench --bench booleans`.
+
+extern crate rscsg;
+
+use rscsg::dim3::{BspNode, Csg, Vector};
+use std::time::{Duration, Instant};
+
+const RUNS: u32 = 10;
+
+fn time<F: FnMut()>(mut func: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        func();
+    }
+    start.elapsed() / RUNS
+}
+
+fn main() {
+    let sphere = Csg::sphere(10., 32, 16);
+    let cube = Csg::cube(Vector(1., 1., 1.), true);
+
+    // Only the faces of the sphere near the cube take part in the booleans
+    let edge = cube.clone().translate(Vector(10., 0., 0.));
+    println!(
+        "tree of the sphere:       {:?}",
+        time(|| {
+            BspNode::new(Some(sphere.polygons.clone()));
+        })
+    );
+    println!(
+        "union at the edge:        {:?}",
+        time(|| {
+            Csg::union(&sphere, &edge);
+        })
+    );
+    println!(
+        "subtract at the edge:     {:?}",
+        time(|| {
+            Csg::subtract(&sphere, &edge);
+        })
+    );
+
+    // No face of the sphere comes near the cube
+    println!(
+        "union at the center:      {:?}",
+        time(|| {
+            Csg::union(&sphere, &cube);
+        })
+    );
+}
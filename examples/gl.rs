@@ -3,6 +3,7 @@ extern crate rscsg;
 
 use lingo::{draw, gl, window};
 use rscsg::dim3::{Csg, Vector};
+use rscsg::Unit;
 use std::mem::size_of;
 
 const SHADER_VERT: &'static str = r#"
@@ -74,7 +75,7 @@ fn scene_cube(_step: i32) -> Csg {
 }
 
 fn scene_cubes(step: i32) -> Csg {
-    let rotate = 30. + (step * 4) as f32;
+    let rotate = 30. + (step * 4) as Unit;
     Csg::union(
         &Csg::cube(Vector(1., 1., 1.), true).rotate(Vector(1., 0., 0.), rotate),
         &Csg::cube(Vector(1., 1., 1.), false),
@@ -82,7 +83,7 @@ fn scene_cubes(step: i32) -> Csg {
 }
 
 fn scene_cubes_difference(step: i32) -> Csg {
-    let rotate = 30. + (step * 4) as f32;
+    let rotate = 30. + (step * 4) as Unit;
     Csg::subtract(
         &Csg::cube(Vector(1., 1., 1.), true).rotate(Vector(1., 0., 0.), rotate),
         &Csg::cube(Vector(1., 1., 1.), false),
@@ -90,7 +91,7 @@ fn scene_cubes_difference(step: i32) -> Csg {
 }
 
 fn scene_cut_cube(step: i32) -> Csg {
-    let cut_x = 1. + 0.1 * step as f32;
+    let cut_x = 1. + 0.1 * step as Unit;
     Csg::union(
         &Csg::cube(Vector(2., 2., 2.), true),
         &Csg::cube(Vector(1., 3., 3.), false).translate(Vector(cut_x, -1.5, -1.5)),
@@ -112,9 +113,9 @@ fn make_scene(csg: Csg) -> Result<Scene, String> {
     let mut verts = draw::HwBuf::new(vertex_count, draw::Usage::Static)?;
     for triangle in triangles {
         let [p0, p1, p2] = triangle.positions;
-        verts.push(Vertex(p0.0, p0.1, p0.2, 1, 0, 0, 0));
-        verts.push(Vertex(p1.0, p1.1, p1.2, 0, 1, 0, 0));
-        verts.push(Vertex(p2.0, p2.1, p2.2, 0, 0, 1, 0));
+        verts.push(Vertex(p0.0 as f32, p0.1 as f32, p0.2 as f32, 1, 0, 0, 0));
+        verts.push(Vertex(p1.0 as f32, p1.1 as f32, p1.2 as f32, 0, 1, 0, 0));
+        verts.push(Vertex(p2.0 as f32, p2.1 as f32, p2.2 as f32, 0, 0, 1, 0));
     }
     verts.prepear_graphics();
     draw::print_gl_error()?;
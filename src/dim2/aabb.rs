@@ -0,0 +1,79 @@
+use dim2::{Line, Point};
+use Unit;
+
+/// Axis aligned bounding box, from the lowest to the highest coordinate on each axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Aabb {
+    /// Smallest box around all end points of `lines`, nothing if there are none.
+    pub fn from_lines(lines: &[Line]) -> Option<Aabb> {
+        let mut points = lines.iter().flat_map(|line| [line.p0, line.p1]);
+
+        let first = points.next()?;
+        Some(points.fold(
+            Aabb {
+                min: first,
+                max: first,
+            },
+            |bb, p| Aabb {
+                min: Point(bb.min.0.min(p.0), bb.min.1.min(p.1)),
+                max: Point(bb.max.0.max(p.0), bb.max.1.max(p.1)),
+            },
+        ))
+    }
+
+    /// Grown by `margin` in all directions.
+    pub fn expand(&self, margin: Unit) -> Aabb {
+        let m = Point(margin, margin);
+        Aabb {
+            min: self.min - m,
+            max: self.max + m,
+        }
+    }
+
+    /// True if the boxes share any point, touching included.
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.0 <= other.max.0
+            && self.min.1 <= other.max.1
+            && other.min.0 <= self.max.0
+            && other.min.1 <= self.max.1
+    }
+
+    /// True if `p` is inside the box or on its boundary.
+    pub fn contains(&self, p: Point) -> bool {
+        self.min.0 <= p.0 && self.min.1 <= p.1 && p.0 <= self.max.0 && p.1 <= self.max.1
+    }
+
+    /// Smallest box around both boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            min: Point(self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: Point(self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+
+    /// Box shared by both boxes, nothing if they do not overlap.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        Some(Aabb {
+            min: Point(self.min.0.max(other.min.0), self.min.1.max(other.min.1)),
+            max: Point(self.max.0.min(other.max.0), self.max.1.min(other.max.1)),
+        })
+    }
+
+    pub fn center(&self) -> Point {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Extent along each axis.
+    pub fn size(&self) -> Point {
+        self.max - self.min
+    }
+}
@@ -1,4 +1,6 @@
+use dim2::exact::{Anchor, Piece, Shape};
 use dim2::{Line, Plane};
+use CsgOptions;
 
 #[derive(Clone)]
 pub struct BspNode {
@@ -6,15 +8,28 @@ pub struct BspNode {
     pub front: Option<Box<BspNode>>,
     pub back: Option<Box<BspNode>>,
     pub lines: Vec<Line>,
+    pub options: CsgOptions,
+    /// End points of the line `plane` was taken from. Used in robust mode.
+    anchor: Option<Anchor>,
+    /// Exact ends of `lines` in robust mode.
+    shapes: Vec<Shape>,
 }
 
 impl BspNode {
     pub fn new(lines: Option<Vec<Line>>) -> BspNode {
+        BspNode::with_options(lines, CsgOptions::default())
+    }
+
+    /// Tree splitting with the tolerance in `options`, inherited by all child nodes.
+    pub fn with_options(lines: Option<Vec<Line>>, options: CsgOptions) -> BspNode {
         let mut bsp = BspNode {
             plane: None,
             front: None,
             back: None,
             lines: Vec::new(),
+            options,
+            anchor: None,
+            shapes: Vec::new(),
         };
 
         match lines {
@@ -26,11 +41,19 @@ impl BspNode {
 
     pub fn invert(&mut self) {
         for l in self.lines.iter_mut() {
-            l.flip();
+            *l = l.flip();
+        }
+
+        for shape in self.shapes.iter_mut() {
+            shape.flip();
         }
 
         if self.plane.is_some() {
-            self.plane.as_mut().unwrap().flip();
+            self.plane = Some(self.plane.as_ref().unwrap().flip());
+        }
+
+        if let Some(ref mut anchor) = self.anchor {
+            anchor.swap(0, 1);
         }
 
         if self.front.is_some() {
@@ -49,18 +72,22 @@ impl BspNode {
     }
 
     pub fn clip_lines(&mut self, lines: &Vec<Line>) -> Vec<Line> {
+        lines_of(self.clip_pieces(pieces_of(lines.clone())))
+    }
+
+    fn clip_pieces(&self, pieces: Vec<Piece>) -> Vec<Piece> {
         if self.plane.is_none() {
-            return self.lines.clone();
+            return pieces;
         }
 
-        let mut front: Vec<Line> = Vec::new();
-        let mut back: Vec<Line> = Vec::new();
+        let mut front: Vec<Piece> = Vec::new();
+        let mut back: Vec<Piece> = Vec::new();
 
-        for mut line in lines {
-            let mut second_front: Vec<Line> = Vec::new();
-            let mut second_back: Vec<Line> = Vec::new();
-            self.plane.as_mut().unwrap().split_lines(
-                line.clone(),
+        for piece in &pieces {
+            let mut second_front: Vec<Piece> = Vec::new();
+            let mut second_back: Vec<Piece> = Vec::new();
+            self.split_piece(
+                piece,
                 &mut front,
                 &mut back,
                 &mut second_front,
@@ -70,16 +97,14 @@ impl BspNode {
             back.append(&mut second_back);
         }
 
-        let mut front = if self.front.is_some() {
-            self.front.as_mut().unwrap().clip_lines(&mut front)
-        } else {
-            front
+        let mut front = match self.front {
+            Some(ref node) => node.clip_pieces(front),
+            None => front,
         };
 
-        let mut back = if self.back.is_some() {
-            self.back.as_mut().unwrap().clip_lines(&mut back)
-        } else {
-            Vec::new()
+        let mut back = match self.back {
+            Some(ref node) => node.clip_pieces(back),
+            None => Vec::new(),
         };
 
         front.append(&mut back);
@@ -87,7 +112,10 @@ impl BspNode {
     }
 
     pub fn clip_to(&mut self, bsp: &mut BspNode) {
-        self.lines = bsp.clip_lines(&self.lines);
+        let clipped = bsp.clip_pieces(self.pieces());
+        self.lines.clear();
+        self.shapes.clear();
+        self.add_pieces(clipped);
 
         if self.front.is_some() {
             self.front.as_mut().unwrap().clip_to(bsp);
@@ -99,7 +127,7 @@ impl BspNode {
     }
 
     pub fn all_lines(&self) -> Vec<Line> {
-        let mut lines: Vec<Line> = self.lines.clone();
+        let mut lines: Vec<Line> = Vec::new();
         self.fill_lines(&mut lines);
         lines
     }
@@ -116,48 +144,141 @@ impl BspNode {
         }
     }
 
+    /// `all_lines` with their exact ends in robust mode.
+    pub(crate) fn all_pieces(&self) -> Vec<Piece> {
+        let mut pieces = self.pieces();
+
+        if let Some(ref front) = self.front {
+            pieces.append(&mut front.all_pieces());
+        }
+
+        if let Some(ref back) = self.back {
+            pieces.append(&mut back.all_pieces());
+        }
+
+        pieces
+    }
+
+    fn pieces(&self) -> Vec<Piece> {
+        if self.shapes.len() == self.lines.len() {
+            self.lines
+                .iter()
+                .zip(self.shapes.iter())
+                .map(|(line, &shape)| Piece {
+                    line: line.clone(),
+                    shape: Some(shape),
+                })
+                .collect()
+        } else {
+            pieces_of(self.lines.clone())
+        }
+    }
+
+    fn add_pieces(&mut self, pieces: Vec<Piece>) {
+        for piece in pieces {
+            self.lines.push(piece.line);
+            self.shapes.extend(piece.shape);
+        }
+    }
+
     pub fn build(&mut self, lines: Vec<Line>) {
-        if lines.len() == 0 {
+        self.build_pieces(pieces_of(lines));
+    }
+
+    /// `build` keeping the exact ends of `pieces` in robust mode.
+    pub(crate) fn build_pieces(&mut self, pieces: Vec<Piece>) {
+        let pieces: Vec<Piece> = if self.options.robust {
+            pieces.into_iter().filter_map(Piece::shaped).collect()
+        } else {
+            pieces
+        };
+
+        if pieces.is_empty() {
             return;
         }
 
+        // The line picked for the plane stays here even if rounding puts an end off it,
+        // otherwise it could be passed down forever
+        let mut start = 0;
         if self.plane.is_none() {
-            self.plane = Some(lines[0].plane.clone());
+            self.plane = Some(pieces[0].line.plane.clone());
+            self.anchor = pieces[0].shape.map(|shape| shape.support);
+            self.add_pieces(vec![pieces[0].clone()]);
+            start = 1;
         }
 
-        let plane = self.plane.clone().unwrap();
-
-        self.lines.push(lines[0].clone());
-        let mut front: Vec<Line> = Vec::new();
-        let mut back: Vec<Line> = Vec::new();
+        let mut front: Vec<Piece> = Vec::new();
+        let mut back: Vec<Piece> = Vec::new();
 
-        for line in lines.iter().skip(1) {
-            let mut second: Vec<Line> = Vec::new();
+        for piece in pieces[start..].iter() {
+            let mut coplane_front: Vec<Piece> = Vec::new();
+            let mut coplane_back: Vec<Piece> = Vec::new();
 
-            plane.split_lines(
-                line.clone(),
-                &mut self.lines,
-                &mut second,
+            self.split_piece(
+                piece,
+                &mut coplane_front,
+                &mut coplane_back,
                 &mut front,
                 &mut back,
             );
-            self.lines.append(&mut second);
+            self.add_pieces(coplane_front);
+            self.add_pieces(coplane_back);
         }
 
         if !front.is_empty() {
             if self.front.is_none() {
-                self.front = Some(Box::new(BspNode::new(None)));
+                self.front = Some(Box::new(BspNode::with_options(None, self.options)));
             }
 
-            self.front.as_mut().unwrap().build(front);
+            self.front.as_mut().unwrap().build_pieces(front);
         }
 
         if !back.is_empty() {
             if self.back.is_none() {
-                self.back = Some(Box::new(BspNode::new(None)));
+                self.back = Some(Box::new(BspNode::with_options(None, self.options)));
             }
 
-            self.back.as_mut().unwrap().build(back);
+            self.back.as_mut().unwrap().build_pieces(back);
         }
     }
+
+    /// Split by the plane of this node, decided exactly against the anchor in robust mode.
+    fn split_piece(
+        &self,
+        piece: &Piece,
+        coplane_front: &mut Vec<Piece>,
+        coplane_back: &mut Vec<Piece>,
+        front: &mut Vec<Piece>,
+        back: &mut Vec<Piece>,
+    ) {
+        if let Some(ref anchor) = self.anchor {
+            if self.options.robust {
+                return piece.split(anchor, coplane_front, coplane_back, front, back);
+            }
+        }
+
+        let (mut on_front, mut on_back) = (Vec::new(), Vec::new());
+        let (mut ahead, mut behind) = (Vec::new(), Vec::new());
+        self.plane.as_ref().unwrap().split_lines_with(
+            &self.options,
+            piece.line.clone(),
+            &mut on_front,
+            &mut on_back,
+            &mut ahead,
+            &mut behind,
+        );
+
+        coplane_front.extend(on_front.into_iter().map(Piece::new));
+        coplane_back.extend(on_back.into_iter().map(Piece::new));
+        front.extend(ahead.into_iter().map(Piece::new));
+        back.extend(behind.into_iter().map(Piece::new));
+    }
+}
+
+fn pieces_of(lines: Vec<Line>) -> Vec<Piece> {
+    lines.into_iter().map(Piece::new).collect()
+}
+
+fn lines_of(pieces: Vec<Piece>) -> Vec<Line> {
+    pieces.into_iter().map(|piece| piece.line).collect()
 }
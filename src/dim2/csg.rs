@@ -1,5 +1,6 @@
-use dim2::{BspNode, Line, Point};
-use {Unit, UNIT_PI};
+use dim2::{Aabb, BspNode, Line, Point};
+use std::slice;
+use {CsgOptions, Unit, UNIT_PI};
 
 #[derive(Clone)]
 pub struct Csg {
@@ -19,7 +20,14 @@ impl Csg {
         self.lines.clone()
     }
 
+    /// Bounding box around all lines, nothing for an empty `Csg`.
+    pub fn bounds(&self) -> Option<Aabb> {
+        Aabb::from_lines(&self.lines)
+    }
+
     /// Transformations
+    ///
+    /// Line direction is kept, so a `func` that mirrors the plane leaves the shape inside out.
     pub fn transform_points<F>(mut self, func: F) -> Csg
     where
         F: Fn(Point) -> Point,
@@ -39,35 +47,105 @@ impl Csg {
 
     /// Rotate around origo
     pub fn rotate(self, angle_deg: Unit) -> Csg {
-        let rad = UNIT_PI * angle_deg / 180f32;
+        let rad = UNIT_PI * angle_deg / 180.;
         let s = rad.sin();
         let c = rad.sin();
 
         self.transform_points(|p| Point(c * p.0 + s * p.1, s * p.0 + c * p.1))
     }
 
-    /// Scale around origo
+    /// Scale around origo. One negative factor mirrors the shape, the lines are reversed so it
+    /// still faces outwards.
     pub fn scale(self, scale_axises: Point) -> Csg {
-        self.transform_points(|p| Point(p.0 * scale_axises.0, p.1 * scale_axises.1))
+        let csg = self.transform_points(|p| Point(p.0 * scale_axises.0, p.1 * scale_axises.1));
+
+        if scale_axises.0 * scale_axises.1 < 0. {
+            csg.inverse()
+        } else {
+            csg
+        }
+    }
+
+    /// Mirror through the line through origo with normal `line_normal`. The lines are reversed
+    /// so the shape still faces outwards.
+    pub fn mirror(self, line_normal: Point) -> Csg {
+        let n = line_normal.normalize();
+
+        self.transform_points(|p| p - n * (2. * p.dot(n))).inverse()
     }
 
     pub fn union(a: &Csg, b: &Csg) -> Csg {
-        let mut bsp_a = BspNode::new(Some(a.lines.clone()));
-        let mut bsp_b = BspNode::new(Some(b.lines.clone()));
+        Csg::union_with(a, b, &CsgOptions::default())
+    }
+
+    /// `union` with the tolerance in `options`.
+    pub fn union_with(a: &Csg, b: &Csg, options: &CsgOptions) -> Csg {
+        let overlap = match overlap_bounds(a, b, options) {
+            Some(overlap) => overlap,
+            None => {
+                let mut lines = a.lines.clone();
+                lines.extend(b.lines.iter().cloned());
+                return Csg::from_lines(lines);
+            }
+        };
+
+        // Lines outside the overlap are outside the other shape and pass through
+        let (a_in, mut lines) = split_by_bounds(&a.lines, &overlap);
+        let (b_in, mut b_out) = split_by_bounds(&b.lines, &overlap);
+        lines.append(&mut b_out);
+
+        // Without faces of one shape in the overlap, the other is wholly inside or outside of it
+        if a_in.is_empty() || b_in.is_empty() {
+            if !lies_inside(b, &a_in) {
+                lines.extend(a_in);
+            }
+            if !lies_inside(a, &b_in) {
+                lines.extend(b_in);
+            }
+            return Csg::from_lines(lines);
+        }
+
+        let mut bsp_a = BspNode::with_options(Some(a_in), *options);
+        let mut bsp_b = BspNode::with_options(Some(b_in), *options);
 
         bsp_a.clip_to(&mut bsp_b);
         bsp_b.clip_to(&mut bsp_a);
         bsp_b.invert();
         bsp_b.clip_to(&mut bsp_a);
         bsp_b.invert();
-        bsp_a.build(bsp_b.all_lines());
+        bsp_a.build_pieces(bsp_b.all_pieces());
 
-        Csg::from_lines(bsp_a.all_lines())
+        lines.append(&mut bsp_a.all_lines());
+        Csg::from_lines(lines)
     }
 
     pub fn subtract(a: &Csg, b: &Csg) -> Csg {
-        let mut bsp_a = BspNode::new(Some(a.lines.clone()));
-        let mut bsp_b = BspNode::new(Some(b.lines.clone()));
+        Csg::subtract_with(a, b, &CsgOptions::default())
+    }
+
+    /// `subtract` with the tolerance in `options`.
+    pub fn subtract_with(a: &Csg, b: &Csg, options: &CsgOptions) -> Csg {
+        let overlap = match overlap_bounds(a, b, options) {
+            Some(overlap) => overlap,
+            None => return a.clone(),
+        };
+
+        // Lines of `a` outside the overlap are kept as they are, those of `b` are dropped
+        let (a_in, mut lines) = split_by_bounds(&a.lines, &overlap);
+        let (b_in, _) = split_by_bounds(&b.lines, &overlap);
+
+        if a_in.is_empty() || b_in.is_empty() {
+            if !lies_inside(b, &a_in) {
+                lines.extend(a_in);
+            }
+            if lies_inside(a, &b_in) {
+                lines.extend(b_in.iter().map(Line::flip));
+            }
+            return Csg::from_lines(lines);
+        }
+
+        let mut bsp_a = BspNode::with_options(Some(a_in), *options);
+        let mut bsp_b = BspNode::with_options(Some(b_in), *options);
 
         bsp_a.invert();
         bsp_a.clip_to(&mut bsp_b);
@@ -75,22 +153,48 @@ impl Csg {
         bsp_b.invert();
         bsp_b.clip_to(&mut bsp_a);
         bsp_b.invert();
-        bsp_a.build(bsp_b.all_lines());
+        bsp_a.build_pieces(bsp_b.all_pieces());
         bsp_a.invert();
 
-        Csg::from_lines(bsp_a.all_lines())
+        lines.append(&mut bsp_a.all_lines());
+        Csg::from_lines(lines)
     }
 
     pub fn intersect(a: &Csg, b: &Csg) -> Csg {
-        let mut bsp_a = BspNode::new(Some(a.lines.clone()));
-        let mut bsp_b = BspNode::new(Some(b.lines.clone()));
+        Csg::intersect_with(a, b, &CsgOptions::default())
+    }
+
+    /// `intersect` with the tolerance in `options`.
+    pub fn intersect_with(a: &Csg, b: &Csg, options: &CsgOptions) -> Csg {
+        let overlap = match overlap_bounds(a, b, options) {
+            Some(overlap) => overlap,
+            None => return Csg::new(),
+        };
+
+        // Nothing outside the overlap is kept
+        let (a_in, _) = split_by_bounds(&a.lines, &overlap);
+        let (b_in, _) = split_by_bounds(&b.lines, &overlap);
+
+        if a_in.is_empty() || b_in.is_empty() {
+            let mut lines = Vec::new();
+            if lies_inside(b, &a_in) {
+                lines.extend(a_in);
+            }
+            if lies_inside(a, &b_in) {
+                lines.extend(b_in);
+            }
+            return Csg::from_lines(lines);
+        }
+
+        let mut bsp_a = BspNode::with_options(Some(a_in), *options);
+        let mut bsp_b = BspNode::with_options(Some(b_in), *options);
 
         bsp_a.invert();
         bsp_b.clip_to(&mut bsp_a);
         bsp_b.invert();
         bsp_a.clip_to(&mut bsp_b);
         bsp_b.clip_to(&mut bsp_a);
-        bsp_a.build(bsp_b.all_lines());
+        bsp_a.build_pieces(bsp_b.all_pieces());
         bsp_a.invert();
         Csg::from_lines(bsp_a.all_lines())
     }
@@ -98,8 +202,51 @@ impl Csg {
     pub fn inverse(&self) -> Csg {
         let mut csg = self.clone();
         for line in csg.lines.iter_mut() {
-            line.flip();
+            *line = line.flip();
         }
         csg
     }
 }
+
+/// Box shared by the bounds of `a` and `b` grown by twice the tolerance, so lines touching the
+/// other shape lie well inside it. Nothing if the bounds do not overlap.
+fn overlap_bounds(a: &Csg, b: &Csg, options: &CsgOptions) -> Option<Aabb> {
+    let margin = 2. * options.epsilon;
+    a.bounds()?
+        .expand(margin)
+        .intersection(&b.bounds()?.expand(margin))
+}
+
+/// Lines reaching into `bounds` and the others. The BSP trees of a boolean only get the ones
+/// reaching into the overlap of both shapes, the others are outside the other shape. Lines are not
+/// cut at the box, so no new points end up in the middle of the lines passed by.
+fn split_by_bounds(lines: &[Line], bounds: &Aabb) -> (Vec<Line>, Vec<Line>) {
+    lines.iter().cloned().partition(|line| {
+        Aabb::from_lines(slice::from_ref(line)).is_some_and(|bb| bb.overlaps(bounds))
+    })
+}
+
+/// True if `pieces` lie inside `shape`, judged by the middle of the first of them. Only
+/// meaningful where `shape` has no lines, so all of them are on the same side.
+fn lies_inside(shape: &Csg, pieces: &[Line]) -> bool {
+    let line = match pieces.first() {
+        Some(line) => line,
+        None => return false,
+    };
+    let middle = (line.p0 + line.p1) * 0.5;
+    let cross = |u: Point, v: Point| u.0 * v.1 - u.1 * v.0;
+
+    // Seen from an inside point the outline turns once around it, from an outside point not at
+    // all. Unlike counting the lines a ray crosses, adding up the turns does not depend on missing
+    // the corners between them.
+    let turned: Unit = shape
+        .lines
+        .iter()
+        .map(|line| {
+            let (u, v) = (line.p0 - middle, line.p1 - middle);
+            cross(u, v).atan2(u.0 * v.0 + u.1 * v.1)
+        })
+        .sum();
+
+    turned.abs() > UNIT_PI
+}
@@ -0,0 +1,238 @@
+//! Lines with exactly known ends, split by BSP trees in robust mode. Ends cut from a line are kept
+//! as the line they were cut with instead of their rounded position, so the side of a line they
+//! lie on is decided exactly by the predicates however often they are split again. Positions are
+//! rounded for the output lines only.
+
+use dim2::{Line, Point};
+use predicates::{floor_quotient, orient2d, sign, wide, Arithmetic, Polynomial};
+use std::cmp::Ordering;
+
+/// Two points spanning a line, the front is on the left going from the first to the second.
+pub type Anchor = [Point; 2];
+
+/// Line as kept in a BSP tree, with its exact ends in robust mode.
+#[derive(Clone)]
+pub struct Piece {
+    pub line: Line,
+    pub shape: Option<Shape>,
+}
+
+/// Exact ends of a line lying on `support`.
+#[derive(Clone, Copy)]
+pub struct Shape {
+    pub support: Anchor,
+    ends: [End; 2],
+}
+
+#[derive(Clone, Copy)]
+enum End {
+    Given(Point),
+    /// Where the support crosses the line through two given points.
+    Cross(Anchor),
+}
+
+impl Piece {
+    pub fn new(line: Line) -> Piece {
+        Piece { line, shape: None }
+    }
+
+    /// This piece with its exact ends. Lines without length are left out, they span no line.
+    pub fn shaped(self) -> Option<Piece> {
+        if self.shape.is_some() {
+            return Some(self);
+        }
+
+        let (p0, p1) = (self.line.p0, self.line.p1);
+        if (p0.0, p0.1) == (p1.0, p1.1) {
+            return None;
+        }
+
+        Some(Piece {
+            shape: Some(Shape {
+                support: [p0, p1],
+                ends: [End::Given(p0), End::Given(p1)],
+            }),
+            ..self
+        })
+    }
+
+    /// Sort by the line through `plane` like `Plane::split_lines`, deciding sides exactly. Pieces
+    /// keep the plane of this line.
+    pub fn split(
+        &self,
+        plane: &Anchor,
+        coplane_front: &mut Vec<Piece>,
+        coplane_back: &mut Vec<Piece>,
+        front: &mut Vec<Piece>,
+        back: &mut Vec<Piece>,
+    ) {
+        let shape = match self.shape {
+            Some(shape) => shape,
+            None => {
+                if let Some(piece) = self.clone().shaped() {
+                    piece.split(plane, coplane_front, coplane_back, front, back);
+                }
+                return;
+            }
+        };
+
+        let support = &shape.support;
+        let sides = [
+            shape.ends[0].side(support, plane),
+            shape.ends[1].side(support, plane),
+        ];
+
+        match (sides[0], sides[1]) {
+            (Ordering::Equal, Ordering::Equal) => {
+                if facing(support, plane) {
+                    coplane_front.push(self.clone());
+                } else {
+                    coplane_back.push(self.clone());
+                }
+            }
+            (Ordering::Less, Ordering::Greater) | (Ordering::Greater, Ordering::Less) => {
+                let cross = End::Cross(*plane);
+                let point = cross.position(support);
+                let piece = |ends: [End; 2], p0: Point, p1: Point| Piece {
+                    line: Line {
+                        p0,
+                        p1,
+                        plane: self.line.plane.clone(),
+                    },
+                    shape: Some(Shape {
+                        support: *support,
+                        ends,
+                    }),
+                };
+
+                let first = piece([shape.ends[0], cross], self.line.p0, point);
+                let second = piece([cross, shape.ends[1]], point, self.line.p1);
+                if sides[0] == Ordering::Greater {
+                    front.push(first);
+                    back.push(second);
+                } else {
+                    back.push(first);
+                    front.push(second);
+                }
+            }
+            _ => {
+                if sides[0] == Ordering::Greater || sides[1] == Ordering::Greater {
+                    front.push(self.clone());
+                } else {
+                    back.push(self.clone());
+                }
+            }
+        }
+    }
+}
+
+impl Shape {
+    /// Reverse the direction, like `Line::flip`.
+    pub fn flip(&mut self) {
+        self.support.swap(0, 1);
+        self.ends.swap(0, 1);
+    }
+}
+
+impl End {
+    /// Side of the line through `plane` this end of a line on `support` lies on, `Equal` exactly
+    /// on it.
+    fn side(&self, support: &Anchor, plane: &Anchor) -> Ordering {
+        match *self {
+            End::Given(p) => {
+                let d = orient2d(coords(plane[0]), coords(plane[1]), coords(p));
+                d.partial_cmp(&0.).unwrap_or(Ordering::Equal)
+            }
+            End::Cross(ref cut) => {
+                let side = sign(&Side(support, cut, plane));
+                if sign(&Weight(support, cut)) == Ordering::Less {
+                    side.reverse()
+                } else {
+                    side
+                }
+            }
+        }
+    }
+
+    /// Position rounded down from the exact one, so pieces sharing an end get the same position
+    /// for it whichever lines they were cut by.
+    fn position(&self, support: &Anchor) -> Point {
+        match *self {
+            End::Given(p) => p,
+            End::Cross(ref cut) => {
+                let coordinate =
+                    |axis| floor_quotient(&Coordinate(support, cut, axis), &Weight(support, cut));
+                Point(coordinate(0), coordinate(1))
+            }
+        }
+    }
+}
+
+/// Coordinates and a weight dividing them into the point where `support` crosses the line
+/// through `cut`, exactly in `T`.
+fn homogeneous<T: Arithmetic>(support: &Anchor, cut: &Anchor) -> ([T; 2], T) {
+    let (from, to) = (value::<T>(cut, support[0]), value::<T>(cut, support[1]));
+    let (u, v) = (lift::<T>(support[0]), lift::<T>(support[1]));
+    let coordinate = |axis: usize| from.clone() * v[axis].clone() - to.clone() * u[axis].clone();
+
+    ([coordinate(0), coordinate(1)], from - to)
+}
+
+/// True if the lines through `a` and `b`, known to be the same, run the same way.
+fn facing(a: &Anchor, b: &Anchor) -> bool {
+    let (da, db) = (direction::<f64>(a), direction::<f64>(b));
+    da[0] * db[0] + da[1] * db[1] > 0.
+}
+
+fn coords(p: Point) -> [f64; 2] {
+    [wide(p.0), wide(p.1)]
+}
+
+fn lift<T: Arithmetic>(p: Point) -> [T; 2] {
+    [T::from_f64(wide(p.0)), T::from_f64(wide(p.1))]
+}
+
+fn direction<T: Arithmetic>(anchor: &Anchor) -> [T; 2] {
+    let (a, b) = (lift::<T>(anchor[0]), lift::<T>(anchor[1]));
+    [b[0].clone() - a[0].clone(), b[1].clone() - a[1].clone()]
+}
+
+/// `orient2d` of `p` against the line through `anchor`, in `T`.
+fn value<T: Arithmetic>(anchor: &Anchor, p: Point) -> T {
+    let d = direction::<T>(anchor);
+    let (a, p) = (lift::<T>(anchor[0]), lift::<T>(p));
+    d[0].clone() * (p[1].clone() - a[1].clone()) - d[1].clone() * (p[0].clone() - a[0].clone())
+}
+
+/// Value of the line through the last anchor like `value`, at the homogeneous coordinates of the
+/// point where the first crosses the second. Its sign is the side the point lies on if the weight
+/// is positive.
+struct Side<'a>(&'a Anchor, &'a Anchor, &'a Anchor);
+
+impl<'a> Polynomial for Side<'a> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        let (n, w) = homogeneous::<T>(self.0, self.1);
+        let d = direction::<T>(self.2);
+        let a = lift::<T>(self.2[0]);
+
+        d[0].clone() * (n[1].clone() - w.clone() * a[1].clone())
+            - d[1].clone() * (n[0].clone() - w * a[0].clone())
+    }
+}
+
+struct Weight<'a>(&'a Anchor, &'a Anchor);
+
+impl<'a> Polynomial for Weight<'a> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        homogeneous::<T>(self.0, self.1).1
+    }
+}
+
+struct Coordinate<'a>(&'a Anchor, &'a Anchor, usize);
+
+impl<'a> Polynomial for Coordinate<'a> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        let (n, _) = homogeneous::<T>(self.0, self.1);
+        n[self.2].clone()
+    }
+}
@@ -41,6 +41,13 @@ impl LineStrip {
             lines.push(Line::new(self.points[i - 1], self.points[i]));
         }
 
+        if self.enclosed && self.points.len() > 2 {
+            lines.push(Line::new(
+                self.points[self.points.len() - 1],
+                self.points[0],
+            ));
+        }
+
         lines
     }
 }
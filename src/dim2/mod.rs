@@ -1,15 +1,22 @@
+mod aabb;
 mod bsp_node;
 mod csg;
+mod exact;
 mod line;
 mod line_strip;
 mod plane;
 mod point;
+mod region;
 mod shapes;
+mod triangulate;
 
+pub use self::aabb::Aabb;
 pub use self::bsp_node::BspNode;
 pub use self::csg::Csg;
 pub use self::line::Line;
 pub use self::line_strip::LineStrip;
 pub use self::plane::Plane;
 pub use self::point::Point;
+pub use self::region::Region;
 pub use self::shapes::{circle, rectangle};
+pub use self::triangulate::{signed_area, triangulate, triangulate_with_holes};
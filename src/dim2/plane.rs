@@ -1,5 +1,5 @@
-use dim2::{Line, LineStrip, Point};
-use {Unit, EPSILON};
+use dim2::{Line, Point};
+use {CsgOptions, Unit};
 
 bitflags! {
     struct Location: u32 {
@@ -34,6 +34,27 @@ impl Plane {
         front: &mut Collector,
         back: &mut Collector,
     ) {
+        self.split_lines_with(
+            &CsgOptions::default(),
+            line,
+            coplane_front,
+            coplane_back,
+            front,
+            back,
+        );
+    }
+
+    /// Like `split_lines`, with the tolerance taken from `options`.
+    pub fn split_lines_with(
+        &self,
+        options: &CsgOptions,
+        line: Line,
+        coplane_front: &mut Collector,
+        coplane_back: &mut Collector,
+        front: &mut Collector,
+        back: &mut Collector,
+    ) {
+        let epsilon = options.epsilon;
         let mut polygon_type = Location::NONE;
         let mut point_locs = vec![Location::NONE; 2];
 
@@ -41,9 +62,9 @@ impl Plane {
             let t = self.0.dot(point) - self.1;
 
             let loc = {
-                if t < -EPSILON {
+                if t < -epsilon {
                     Location::BACK
-                } else if t > EPSILON {
+                } else if t > epsilon {
                     Location::FRONT
                 } else {
                     Location::COPLANAR
@@ -56,7 +77,7 @@ impl Plane {
 
         match polygon_type {
             Location::COPLANAR => {
-                if self.0.dot(line.p0) > (0 as Unit) {
+                if self.0.dot(line.plane.0) > (0 as Unit) {
                     coplane_front.push(line);
                 } else {
                     coplane_back.push(line);
@@ -65,34 +86,15 @@ impl Plane {
             Location::FRONT => front.push(line),
             Location::BACK => back.push(line),
             Location::SPANNING => {
-                let mut f: Vec<Point> = Vec::new();
-                let mut b: Vec<Point> = Vec::new();
+                let t = (self.1 - self.0.dot(line.p0)) / self.0.dot(line.p1 - line.p0);
+                let v = line.p0.interpolate(&line.p1, t);
 
-                for (i, (p0, p1)) in [(line.p0, line.p1), (line.p1, line.p0)].iter().enumerate() {
-                    let j = (i + 1) & 0b1;
-                    if point_locs[i] != Location::BACK {
-                        f.push(line.p0);
-                    }
-
-                    if point_locs[i] != Location::FRONT {
-                        b.push(line.p0);
-                    }
-
-                    if (point_locs[i] | point_locs[j]) == Location::SPANNING {
-                        let t = (self.1 - self.0.dot(*p0)) / self.0.dot(*p1 - *p0);
-
-                        let v = p0.interpolate(p1, t);
-                        f.push(v);
-                        b.push(v);
-                    }
-                }
-
-                if f.len() >= 2 {
-                    front.append(&mut LineStrip::from_points(f).build_lines());
-                }
-
-                if b.len() >= 2 {
-                    back.append(&mut LineStrip::from_points(b).build_lines());
+                if point_locs[0] == Location::FRONT {
+                    front.push(Line::new(line.p0, v));
+                    back.push(Line::new(v, line.p1));
+                } else {
+                    back.push(Line::new(line.p0, v));
+                    front.push(Line::new(v, line.p1));
                 }
             }
             _ => (),
@@ -1,7 +1,7 @@
 use std::ops::{Add, Div, Mul, Neg, Sub};
 use Unit;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct Point(pub Unit, pub Unit);
 
 impl Point {
@@ -25,7 +25,7 @@ impl Point {
         *self / self.length()
     }
 
-    pub fn interpolate(&self, other: &Point, weight: f32) -> Point {
+    pub fn interpolate(&self, other: &Point, weight: Unit) -> Point {
         *self + (*other - *self) * weight
     }
 }
@@ -0,0 +1,144 @@
+use dim2::{signed_area, triangulate_with_holes, Csg, Point};
+use EPSILON;
+
+/// A filled area of a `Csg`, an outline with zero or more holes. The outline is counter clockwise
+/// and the holes clockwise.
+#[derive(Clone)]
+pub struct Region {
+    pub outline: Vec<Point>,
+    pub holes: Vec<Vec<Point>>,
+}
+
+impl Region {
+    /// Triangles covering the region, counter clockwise and indexing into the outline points
+    /// followed by the points of each hole.
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        triangulate_with_holes(&self.outline, &self.holes)
+    }
+
+    /// All points of the region in the order used by `Region::triangulate`.
+    pub fn points(&self) -> Vec<Point> {
+        let mut points = self.outline.clone();
+        for hole in &self.holes {
+            points.extend(hole.iter().cloned());
+        }
+        points
+    }
+}
+
+/// Even-odd test of `p` against a closed loop.
+fn loop_contains(points: &[Point], p: Point) -> bool {
+    let len = points.len();
+    let mut inside = false;
+
+    for i in 0..len {
+        let a = points[i];
+        let b = points[(i + 1) % len];
+
+        if (a.1 > p.1) != (b.1 > p.1) {
+            let x = a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0);
+            if p.0 < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+impl Csg {
+    /// Chain the lines into closed loops by matching end points. Chains that do not return to
+    /// their start are closed implicitly.
+    pub fn to_loops(&self) -> Vec<Vec<Point>> {
+        let mut unused: Vec<bool> = vec![true; self.lines.len()];
+        let mut loops: Vec<Vec<Point>> = Vec::new();
+
+        for first in 0..self.lines.len() {
+            if !unused[first] {
+                continue;
+            }
+
+            unused[first] = false;
+            let start = self.lines[first].p0;
+            let mut points = vec![start];
+            let mut end = self.lines[first].p1;
+
+            while (end - start).length() > EPSILON {
+                let next = (0..self.lines.len())
+                    .find(|&i| unused[i] && (self.lines[i].p0 - end).length() <= EPSILON);
+
+                match next {
+                    Some(i) => {
+                        unused[i] = false;
+                        points.push(end);
+                        end = self.lines[i].p1;
+                    }
+                    None => {
+                        points.push(end);
+                        break;
+                    }
+                }
+            }
+
+            if points.len() >= 3 {
+                loops.push(points);
+            }
+        }
+
+        loops
+    }
+
+    /// Group the loops into filled regions. Loops inside an even number of other loops are
+    /// outlines, the rest are holes of the closest outline around them.
+    pub fn to_regions(&self) -> Vec<Region> {
+        let loops = self.to_loops();
+        let depths: Vec<usize> = loops
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                (0..loops.len())
+                    .filter(|&j| j != i && loop_contains(&loops[j], l[0]))
+                    .count()
+            })
+            .collect();
+
+        let mut outline_ids: Vec<usize> = Vec::new();
+        let mut regions: Vec<Region> = Vec::new();
+
+        for (i, l) in loops.iter().enumerate() {
+            if depths[i] % 2 != 1 {
+                let mut outline = l.clone();
+                if signed_area(&outline) < 0. {
+                    outline.reverse();
+                }
+
+                outline_ids.push(i);
+                regions.push(Region {
+                    outline,
+                    holes: Vec::new(),
+                });
+            }
+        }
+
+        for (i, l) in loops.iter().enumerate() {
+            if depths[i] % 2 != 1 {
+                continue;
+            }
+
+            let parent = outline_ids
+                .iter()
+                .position(|&o| depths[o] + 1 == depths[i] && loop_contains(&loops[o], l[0]));
+
+            if let Some(parent) = parent {
+                let mut hole = l.clone();
+                if signed_area(&hole) > 0. {
+                    hole.reverse();
+                }
+
+                regions[parent].holes.push(hole);
+            }
+        }
+
+        regions
+    }
+}
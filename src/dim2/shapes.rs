@@ -14,7 +14,8 @@ pub fn rectangle(lo: Point, hi: Point) -> Csg {
 pub fn circle(center: Point, radius: Unit, steps: usize) -> Csg {
     (0..steps)
         .fold(LineStrip::new(), |ls, i| {
-            let angle = ((i as f32) / (steps as f32)) * UNIT_PI * 2f32;
+            // Clockwise, like `rectangle`, so the line normals point outwards
+            let angle = -((i as Unit) / (steps as Unit)) * UNIT_PI * 2.;
 
             ls.line_to(Point(
                 center.0 + radius * angle.cos(),
@@ -0,0 +1,187 @@
+use dim2::Point;
+use {Unit, EPSILON};
+
+/// Twice the signed area of triangle `a`, `b`, `c`. Positive when counter clockwise.
+fn turn(a: Point, b: Point, c: Point) -> Unit {
+    let ab = b - a;
+    let ac = c - a;
+    ab.0 * ac.1 - ab.1 * ac.0
+}
+
+/// Check if segments `a0`-`a1` and `b0`-`b1` cross each other, touching end points excluded.
+fn segments_cross(a0: Point, a1: Point, b0: Point, b1: Point) -> bool {
+    let d0 = turn(a0, a1, b0);
+    let d1 = turn(a0, a1, b1);
+    let d2 = turn(b0, b1, a0);
+    let d3 = turn(b0, b1, a1);
+
+    ((d0 > EPSILON && d1 < -EPSILON) || (d0 < -EPSILON && d1 > EPSILON))
+        && ((d2 > EPSILON && d3 < -EPSILON) || (d2 < -EPSILON && d3 > EPSILON))
+}
+
+/// Twice the signed area of a closed loop of points. Positive when counter clockwise.
+pub fn signed_area(points: &[Point]) -> Unit {
+    let len = points.len();
+    (0..len).fold(0 as Unit, |acc, i| {
+        let p0 = points[i];
+        let p1 = points[(i + 1) % len];
+        acc + p0.0 * p1.1 - p1.0 * p0.1
+    })
+}
+
+/// Ear clip the loop made by `indices` into `points`. The same index may appear more than once,
+/// as it does where holes are bridged to the outline.
+fn clip_ears(points: &[Point], mut remaining: Vec<usize>) -> Vec<[usize; 3]> {
+    let mut triangles: Vec<[usize; 3]> = Vec::new();
+
+    if remaining.len() < 3 {
+        return triangles;
+    }
+
+    let area = (0..remaining.len()).fold(0 as Unit, |acc, i| {
+        let p0 = points[remaining[i]];
+        let p1 = points[remaining[(i + 1) % remaining.len()]];
+        acc + p0.0 * p1.1 - p1.0 * p0.1
+    });
+    let sign: Unit = if area < 0. { -1. } else { 1. };
+    let oriented = |a: usize, b: usize, c: usize| sign * turn(points[a], points[b], points[c]);
+
+    let mut i = 0;
+    let mut stalled = 0;
+
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        i %= len;
+
+        let a = remaining[(i + len - 1) % len];
+        let b = remaining[i];
+        let c = remaining[(i + 1) % len];
+        let t = oriented(a, b, c);
+
+        if t.abs() <= EPSILON {
+            remaining.remove(i);
+            stalled = 0;
+            continue;
+        }
+
+        let is_ear = t > 0.
+            && remaining.iter().all(|&p| {
+                let pos = points[p];
+                p == a
+                    || p == b
+                    || p == c
+                    || [a, b, c]
+                        .iter()
+                        .any(|&q| (points[q] - pos).length() <= EPSILON)
+                    || oriented(a, b, p) < 0.
+                    || oriented(b, c, p) < 0.
+                    || oriented(c, a, p) < 0.
+            });
+
+        // A self intersecting loop may have no ears left, cut a corner anyway to make progress.
+        if is_ear || stalled > len {
+            triangles.push([a, b, c]);
+            remaining.remove(i);
+            stalled = 0;
+        } else {
+            i += 1;
+            stalled += 1;
+        }
+    }
+
+    if oriented(remaining[0], remaining[1], remaining[2]).abs() > EPSILON {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+
+    triangles
+}
+
+/// Split a simple polygon into triangles by ear clipping. The polygon may be concave and wound in
+/// either direction, the returned triangles index into `points` and keep the winding of the
+/// input. Collinear corners are dropped without producing a triangle.
+pub fn triangulate(points: &[Point]) -> Vec<[usize; 3]> {
+    clip_ears(points, (0..points.len()).collect())
+}
+
+/// Triangulate a polygon with holes. `outline` must be counter clockwise and every hole clockwise
+/// and inside the outline. The returned triangles are counter clockwise and index into the
+/// outline points followed by the points of each hole in order.
+pub fn triangulate_with_holes(outline: &[Point], holes: &[Vec<Point>]) -> Vec<[usize; 3]> {
+    let mut points: Vec<Point> = outline.to_vec();
+    let mut merged: Vec<usize> = (0..outline.len()).collect();
+    let mut hole_ranges: Vec<(usize, usize)> = Vec::with_capacity(holes.len());
+
+    for hole in holes {
+        hole_ranges.push((points.len(), points.len() + hole.len()));
+        points.extend(hole.iter().cloned());
+    }
+
+    // Bridge the holes one by one, rightmost first, so earlier bridges rarely block later ones.
+    let rightmost = |&(start, end): &(usize, usize)| {
+        (start..end)
+            .max_by(|&a, &b| points[a].0.partial_cmp(&points[b].0).unwrap())
+            .unwrap_or(start)
+    };
+    hole_ranges.retain(|&(start, end)| end - start >= 3);
+    hole_ranges.sort_by(|a, b| {
+        points[rightmost(b)]
+            .0
+            .partial_cmp(&points[rightmost(a)].0)
+            .unwrap()
+    });
+
+    for (n, range) in hole_ranges.iter().enumerate() {
+        let (start, end) = *range;
+        let from = rightmost(range);
+        let origin = points[from];
+
+        let blocked = |to: Point| {
+            let merged_len = merged.len();
+            let crosses_merged = (0..merged_len).any(|i| {
+                let a = points[merged[i]];
+                let b = points[merged[(i + 1) % merged_len]];
+                segments_cross(origin, to, a, b)
+            });
+            let crosses_hole = hole_ranges[n..].iter().any(|&(s, e)| {
+                (s..e).any(|i| {
+                    let j = if i + 1 == e { s } else { i + 1 };
+                    segments_cross(origin, to, points[i], points[j])
+                })
+            });
+
+            crosses_merged || crosses_hole
+        };
+
+        // Closest visible vertex of what has been merged so far
+        let mut best: Option<(Unit, usize)> = None;
+        for (slot, &index) in merged.iter().enumerate() {
+            let distance = (points[index] - origin).length();
+            let closer = match best {
+                Some((d, _)) => distance < d,
+                None => true,
+            };
+
+            if closer && !blocked(points[index]) {
+                best = Some((distance, slot));
+            }
+        }
+
+        let slot = match best {
+            Some((_, slot)) => slot,
+            None => continue,
+        };
+
+        // Walk from the bridge vertex around the hole and back again
+        let mut splice: Vec<usize> = Vec::with_capacity(end - start + 2);
+        for k in 0..=(end - start) {
+            splice.push(start + (from - start + k) % (end - start));
+        }
+        splice.push(merged[slot]);
+
+        let tail = merged.split_off(slot + 1);
+        merged.extend(splice);
+        merged.extend(tail);
+    }
+
+    clip_ears(&points, merged)
+}
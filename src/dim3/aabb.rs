@@ -0,0 +1,105 @@
+use dim3::{Polygon, Vector};
+use Unit;
+
+/// Axis aligned bounding box, from the lowest to the highest coordinate on each axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: Vector,
+    pub max: Vector,
+}
+
+impl Aabb {
+    /// Smallest box around all vertices of `polygons`, nothing if there are none.
+    pub fn from_polygons(polygons: &[Polygon]) -> Option<Aabb> {
+        let mut positions = polygons
+            .iter()
+            .flat_map(|poly| poly.vertices.iter())
+            .map(|v| v.position);
+
+        let first = positions.next()?;
+        Some(positions.fold(
+            Aabb {
+                min: first,
+                max: first,
+            },
+            |bb, p| Aabb {
+                min: Vector(bb.min.0.min(p.0), bb.min.1.min(p.1), bb.min.2.min(p.2)),
+                max: Vector(bb.max.0.max(p.0), bb.max.1.max(p.1), bb.max.2.max(p.2)),
+            },
+        ))
+    }
+
+    /// Grown by `margin` in all directions.
+    pub fn expand(&self, margin: Unit) -> Aabb {
+        let m = Vector(margin, margin, margin);
+        Aabb {
+            min: self.min - m,
+            max: self.max + m,
+        }
+    }
+
+    /// True if the boxes share any point, touching included.
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.0 <= other.max.0
+            && self.min.1 <= other.max.1
+            && self.min.2 <= other.max.2
+            && other.min.0 <= self.max.0
+            && other.min.1 <= self.max.1
+            && other.min.2 <= self.max.2
+    }
+
+    /// True if `p` is inside the box or on its boundary.
+    pub fn contains(&self, p: Vector) -> bool {
+        self.min.0 <= p.0
+            && self.min.1 <= p.1
+            && self.min.2 <= p.2
+            && p.0 <= self.max.0
+            && p.1 <= self.max.1
+            && p.2 <= self.max.2
+    }
+
+    /// Smallest box around both boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            min: Vector(
+                self.min.0.min(other.min.0),
+                self.min.1.min(other.min.1),
+                self.min.2.min(other.min.2),
+            ),
+            max: Vector(
+                self.max.0.max(other.max.0),
+                self.max.1.max(other.max.1),
+                self.max.2.max(other.max.2),
+            ),
+        }
+    }
+
+    /// Box shared by both boxes, nothing if they do not overlap.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        Some(Aabb {
+            min: Vector(
+                self.min.0.max(other.min.0),
+                self.min.1.max(other.min.1),
+                self.min.2.max(other.min.2),
+            ),
+            max: Vector(
+                self.max.0.min(other.max.0),
+                self.max.1.min(other.max.1),
+                self.max.2.min(other.max.2),
+            ),
+        })
+    }
+
+    pub fn center(&self) -> Vector {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Extent along each axis.
+    pub fn size(&self) -> Vector {
+        self.max - self.min
+    }
+}
@@ -1,25 +1,65 @@
+use dim3::exact::{Anchor, Piece, Shape};
 use dim3::{Plane, Polygon};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use {CsgOptions, SplitHeuristic};
+
+/// Most polygons tried as splitting plane by the exhaustive heuristics.
+const CANDIDATES: usize = 32;
+
+/// Tree levels whose subtrees are built and clipped in parallel, 2^8 tasks at most.
+#[cfg(feature = "parallel")]
+const PARALLEL_DEPTH: usize = 8;
+
+/// Fewer polygons than this are not worth handing to another thread.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_POLYGONS: usize = 64;
 
 /// Holds a node in a BSP tree. A BSP tree is built from a collection of polygons by picking a
 /// polygon to split along. That polygon (and all other coplanar polygons) are added directly to
 /// that node and the other polygons are added to the front and/or back subtrees. This is not a
 /// leafy BSP tree since there is no distinction between internal and leaf nodes.
-
-#[derive(Clone)]
 pub struct BspNode {
     pub plane: Option<Plane>,
     pub front: Option<Box<BspNode>>,
     pub back: Option<Box<BspNode>>,
     pub polygons: Vec<Polygon>,
+    pub options: CsgOptions,
+    /// Triangle of the polygon `plane` was taken from, wound like it. Used in robust mode.
+    anchor: Option<Anchor>,
+    /// Exact outlines of `polygons` in robust mode.
+    shapes: Vec<Shape>,
+    /// Polygons split in two by `plane` while building.
+    splits: usize,
+}
+
+/// Size of a BSP tree, see `BspNode::stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BspStats {
+    pub nodes: usize,
+    /// Nodes on the longest path from the root, one for a single node.
+    pub depth: usize,
+    pub polygons: usize,
+    /// Polygons split in two while building the tree.
+    pub splits: usize,
 }
 
 impl BspNode {
     pub fn new(polygons: Option<Vec<Polygon>>) -> BspNode {
+        BspNode::with_options(polygons, CsgOptions::default())
+    }
+
+    /// Tree splitting with the tolerance in `options`, inherited by all child nodes.
+    pub fn with_options(polygons: Option<Vec<Polygon>>, options: CsgOptions) -> BspNode {
         let mut bsp = BspNode {
             plane: None,
             front: None,
             back: None,
             polygons: Vec::new(),
+            options,
+            anchor: None,
+            shapes: Vec::new(),
+            splits: 0,
         };
 
         match polygons {
@@ -31,39 +71,107 @@ impl BspNode {
 
     /// Convert solid space to empty space and empty space to solid space.
     pub fn invert(&mut self) {
-        for p in self.polygons.iter_mut() {
-            p.flip();
-        }
+        let mut stack: Vec<&mut BspNode> = vec![self];
 
-        if self.plane.is_some() {
-            self.plane.as_mut().unwrap().flip();
+        while let Some(node) = stack.pop() {
+            for p in node.polygons.iter_mut() {
+                p.flip();
+            }
+
+            for shape in node.shapes.iter_mut() {
+                shape.flip();
+            }
+
+            if node.plane.is_some() {
+                node.plane = Some(node.plane.as_ref().unwrap().flip());
+            }
+
+            if let Some(ref mut anchor) = node.anchor {
+                anchor.swap(1, 2);
+            }
+
+            std::mem::swap(&mut node.front, &mut node.back);
+            node.push_children_mut(&mut stack);
         }
+    }
 
-        if self.front.is_some() {
-            self.front.as_mut().unwrap().invert();
+    /// Remove all polygons in `polygons` that are inside this BSP tree.
+    pub fn clip_polygons(&self, polygons: &Vec<Polygon>) -> Vec<Polygon> {
+        polygons_of(self.clip_pieces(pieces_of(polygons.clone())))
+    }
+
+    #[cfg(all(test, feature = "parallel"))]
+    pub(crate) fn clip_polygons_serial(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        polygons_of(self.clip_serial(pieces_of(polygons)))
+    }
+
+    fn clip_pieces(&self, pieces: Vec<Piece>) -> Vec<Piece> {
+        #[cfg(feature = "parallel")]
+        return self.clip_parallel(pieces, PARALLEL_DEPTH);
+
+        #[cfg(not(feature = "parallel"))]
+        return self.clip_serial(pieces);
+    }
+
+    fn clip_serial(&self, pieces: Vec<Piece>) -> Vec<Piece> {
+        if self.plane.is_none() {
+            return pieces;
         }
 
-        if self.back.is_some() {
-            self.back.as_mut().unwrap().invert();
+        let mut result: Vec<Piece> = Vec::new();
+        let mut stack: Vec<(&BspNode, Vec<Piece>)> = vec![(self, pieces)];
+
+        // Front fragments are finished before back fragments, in the order a recursive descent
+        // would produce them
+        while let Some((node, pieces)) = stack.pop() {
+            let (mut front, back) = node.split_all(&pieces);
+
+            if let Some(ref node_back) = node.back {
+                stack.push((node_back, back));
+            }
+
+            match node.front {
+                Some(ref node_front) => stack.push((node_front, front)),
+                None => result.append(&mut front),
+            }
         }
 
-        std::mem::swap(&mut self.front, &mut self.back);
+        result
     }
 
-    /// Recursively remove all polygons in `polygons` that are inside this BSP tree.
-    pub fn clip_polygons(&self, polygons: &Vec<Polygon>) -> Vec<Polygon> {
-        if self.plane.is_none() {
-            return self.polygons.clone();
+    /// Clip the front and back subtrees of the top `depth` levels in parallel, the rest serially.
+    #[cfg(feature = "parallel")]
+    fn clip_parallel(&self, pieces: Vec<Piece>, depth: usize) -> Vec<Piece> {
+        if depth == 0 || pieces.len() < PARALLEL_MIN_POLYGONS || self.plane.is_none() {
+            return self.clip_serial(pieces);
         }
 
-        let mut front: Vec<Polygon> = Vec::new();
-        let mut back: Vec<Polygon> = Vec::new();
+        let (front, back) = self.split_all(&pieces);
+        let (mut front, mut back) = rayon::join(
+            || match self.front {
+                Some(ref node) => node.clip_parallel(front, depth - 1),
+                None => front,
+            },
+            || match self.back {
+                Some(ref node) => node.clip_parallel(back, depth - 1),
+                None => Vec::new(),
+            },
+        );
+
+        front.append(&mut back);
+        front
+    }
+
+    /// Sort `pieces` to the front and back of this node, coplanar ones by their orientation.
+    fn split_all(&self, pieces: &[Piece]) -> (Vec<Piece>, Vec<Piece>) {
+        let mut front: Vec<Piece> = Vec::new();
+        let mut back: Vec<Piece> = Vec::new();
 
-        for poly in polygons {
-            let mut second_front: Vec<Polygon> = Vec::new();
-            let mut second_back: Vec<Polygon> = Vec::new();
-            self.plane.as_ref().unwrap().split_polygon(
-                &poly,
+        for piece in pieces {
+            let mut second_front: Vec<Piece> = Vec::new();
+            let mut second_back: Vec<Piece> = Vec::new();
+            self.split_piece(
+                piece,
                 &mut front,
                 &mut back,
                 &mut second_front,
@@ -73,99 +181,470 @@ impl BspNode {
             back.append(&mut second_back);
         }
 
-        let mut front = if self.front.is_some() {
-            self.front.as_ref().unwrap().clip_polygons(&mut front)
-        } else {
-            front
-        };
-
-        let mut back = if self.back.is_some() {
-            self.back.as_ref().unwrap().clip_polygons(&mut back)
-        } else {
-            Vec::new()
-        };
-
-        front.append(&mut back);
-        front
+        (front, back)
     }
 
+    /// Remove the parts of the polygons of this tree that are inside `bsp`. Nodes are clipped in
+    /// parallel with the `parallel` feature.
     pub fn clip_to(&mut self, bsp: &BspNode) {
-        self.polygons = bsp.clip_polygons(&self.polygons);
-
-        if self.front.is_some() {
-            self.front.as_mut().unwrap().clip_to(bsp);
+        let mut lists: Vec<(&mut Vec<Polygon>, &mut Vec<Shape>)> = Vec::new();
+        let mut stack: Vec<&mut BspNode> = vec![self];
+
+        while let Some(node) = stack.pop() {
+            let BspNode {
+                ref mut polygons,
+                ref mut shapes,
+                ref mut front,
+                ref mut back,
+                ..
+            } = *node;
+
+            lists.push((polygons, shapes));
+            stack.extend(back.iter_mut().map(|child| &mut **child));
+            stack.extend(front.iter_mut().map(|child| &mut **child));
         }
 
-        if self.back.is_some() {
-            self.back.as_mut().unwrap().clip_to(bsp);
+        #[cfg(feature = "parallel")]
+        lists
+            .par_iter_mut()
+            .for_each(|&mut (ref mut polygons, ref mut shapes)| {
+                let clipped = bsp.clip_serial(pieces(polygons, shapes));
+                polygons.clear();
+                shapes.clear();
+                store(clipped, polygons, shapes);
+            });
+
+        #[cfg(not(feature = "parallel"))]
+        for (polygons, shapes) in lists {
+            let clipped = bsp.clip_serial(pieces(polygons, shapes));
+            polygons.clear();
+            shapes.clear();
+            store(clipped, polygons, shapes);
         }
     }
 
     pub fn all_polygons(&self) -> Vec<Polygon> {
         let mut polys: Vec<Polygon> = Vec::new();
-        self.fill_polygons(&mut polys);
+        let mut stack: Vec<&BspNode> = vec![self];
+
+        while let Some(node) = stack.pop() {
+            polys.extend(node.polygons.iter().cloned());
+
+            if let Some(ref back) = node.back {
+                stack.push(back);
+            }
+
+            if let Some(ref front) = node.front {
+                stack.push(front);
+            }
+        }
+
         polys
     }
 
-    fn fill_polygons(&self, polys: &mut Vec<Polygon>) {
-        polys.append(&mut self.polygons.clone());
+    /// `all_polygons` with their exact outlines in robust mode.
+    pub(crate) fn all_pieces(&self) -> Vec<Piece> {
+        let mut pieces: Vec<Piece> = Vec::new();
+        let mut stack: Vec<&BspNode> = vec![self];
+
+        while let Some(node) = stack.pop() {
+            pieces.append(&mut node.pieces());
 
-        if self.front.is_some() {
-            self.front.as_ref().unwrap().fill_polygons(polys);
+            if let Some(ref back) = node.back {
+                stack.push(back);
+            }
+
+            if let Some(ref front) = node.front {
+                stack.push(front);
+            }
         }
 
-        if self.back.is_some() {
-            self.back.as_ref().unwrap().fill_polygons(polys);
+        pieces
+    }
+
+    fn pieces(&self) -> Vec<Piece> {
+        pieces(&self.polygons, &self.shapes)
+    }
+
+    fn add_pieces(&mut self, pieces: Vec<Piece>) {
+        let BspNode {
+            ref mut polygons,
+            ref mut shapes,
+            ..
+        } = *self;
+        store(pieces, polygons, shapes);
+    }
+
+    pub fn stats(&self) -> BspStats {
+        let mut stats = BspStats::default();
+        let mut stack: Vec<(&BspNode, usize)> = vec![(self, 1)];
+
+        while let Some((node, depth)) = stack.pop() {
+            stats.nodes += 1;
+            stats.depth = stats.depth.max(depth);
+            stats.polygons += node.polygons.len();
+            stats.splits += node.splits;
+
+            for child in node.front.iter().chain(node.back.iter()) {
+                stack.push((child, depth + 1));
+            }
         }
+
+        stats
     }
 
     /// Build a BSP tree out of `Vec<Polygon>`. When called on an existing tree, the new polygons
     /// are filtered down to the bottom of the tree and become new nodes there. Each set of
-    /// polygons is partitioned using the first polygon (no heuristic is used to pick a good
-    /// split).
+    /// polygons is partitioned using the plane picked by `options.split`. Nodes are visited with
+    /// an explicit stack, so deep trees do not overflow the call stack. The `parallel` feature
+    /// builds the front and back subtrees of the top levels in parallel, giving the same tree.
     pub fn build(&mut self, polygons: Vec<Polygon>) {
-        if polygons.len() == 0 {
-            return;
+        self.build_pieces(pieces_of(polygons));
+    }
+
+    /// `build` keeping the exact outlines of `pieces` in robust mode.
+    pub(crate) fn build_pieces(&mut self, pieces: Vec<Piece>) {
+        #[cfg(feature = "parallel")]
+        self.build_parallel(pieces, PARALLEL_DEPTH);
+
+        #[cfg(not(feature = "parallel"))]
+        self.build_pieces_serial(pieces);
+    }
+
+    #[cfg(all(test, feature = "parallel"))]
+    pub(crate) fn build_serial(&mut self, polygons: Vec<Polygon>) {
+        self.build_pieces_serial(pieces_of(polygons));
+    }
+
+    fn build_pieces_serial(&mut self, pieces: Vec<Piece>) {
+        let mut stack: Vec<(&mut BspNode, Vec<Piece>)> = vec![(self, pieces)];
+
+        while let Some((node, pieces)) = stack.pop() {
+            let (front, back) = node.build_node(pieces);
+            let (node_front, node_back) = node.children_for(&front, &back);
+
+            if let Some(child) = node_back {
+                stack.push((child, back));
+            }
+
+            if let Some(child) = node_front {
+                stack.push((child, front));
+            }
         }
+    }
 
-        if self.plane.is_none() {
-            self.plane = Some(polygons[0].plane.clone());
+    #[cfg(feature = "parallel")]
+    fn build_parallel(&mut self, pieces: Vec<Piece>, depth: usize) {
+        if depth == 0 || pieces.len() < PARALLEL_MIN_POLYGONS {
+            return self.build_pieces_serial(pieces);
+        }
+
+        let (front, back) = self.build_node(pieces);
+        let (node_front, node_back) = self.children_for(&front, &back);
+
+        rayon::join(
+            || node_front.map(|child| child.build_parallel(front, depth - 1)),
+            || node_back.map(|child| child.build_parallel(back, depth - 1)),
+        );
+    }
+
+    /// Children to build `front` and `back` into, created as needed. Nothing where there are no
+    /// polygons.
+    fn children_for(
+        &mut self,
+        front: &[Piece],
+        back: &[Piece],
+    ) -> (Option<&mut BspNode>, Option<&mut BspNode>) {
+        let options = self.options;
+        let BspNode {
+            front: ref mut node_front,
+            back: ref mut node_back,
+            ..
+        } = *self;
+
+        fn child<'a>(
+            node: &'a mut Option<Box<BspNode>>,
+            pieces: &[Piece],
+            options: CsgOptions,
+        ) -> Option<&'a mut BspNode> {
+            if pieces.is_empty() {
+                None
+            } else {
+                let node =
+                    node.get_or_insert_with(|| Box::new(BspNode::with_options(None, options)));
+                Some(&mut **node)
+            }
+        }
+
+        (
+            child(node_front, front, options),
+            child(node_back, back, options),
+        )
+    }
+
+    /// Keep the polygons lying in the plane of this node, return the ones in front and behind.
+    fn build_node(&mut self, pieces: Vec<Piece>) -> (Vec<Piece>, Vec<Piece>) {
+        let mut front: Vec<Piece> = Vec::new();
+        let mut back: Vec<Piece> = Vec::new();
+
+        let pieces: Vec<Piece> = if self.options.robust {
+            pieces.into_iter().flat_map(Piece::shaped).collect()
+        } else {
+            pieces
+        };
+
+        if pieces.is_empty() {
+            return (front, back);
         }
 
-        let plane = self.plane.clone().unwrap();
+        // The polygon picked for the plane stays here even if rounding puts a vertex off it,
+        // otherwise it could be passed down forever
+        let mut picked = None;
+        if self.plane.is_none() {
+            let i = self.pick_split(&pieces);
+            self.plane = Some(pieces[i].polygon.plane.clone());
+            self.anchor = pieces[i].shape.as_ref().map(|shape| shape.support);
+            self.add_pieces(vec![pieces[i].clone()]);
+            picked = Some(i);
+        }
 
-        let mut front: Vec<Polygon> = Vec::new();
-        let mut back: Vec<Polygon> = Vec::new();
+        for (i, piece) in pieces.iter().enumerate() {
+            if picked == Some(i) {
+                continue;
+            }
 
-        for poly in polygons.iter() {
-            let mut second: Vec<Polygon> = Vec::new();
+            let mut coplane_front: Vec<Piece> = Vec::new();
+            let mut coplane_back: Vec<Piece> = Vec::new();
+            let (ahead, behind) = (front.len(), back.len());
 
-            plane.split_polygon(
-                &poly,
-                &mut self.polygons,
-                &mut second,
+            self.split_piece(
+                piece,
+                &mut coplane_front,
+                &mut coplane_back,
                 &mut front,
                 &mut back,
             );
-            self.polygons.append(&mut second);
+            self.add_pieces(coplane_front);
+            self.add_pieces(coplane_back);
+
+            // Counted once however many pieces it was cut into
+            if front.len() > ahead && back.len() > behind {
+                self.splits += 1;
+            }
+        }
+
+        (front, back)
+    }
+
+    /// Index of the polygon to take the plane of this node from.
+    fn pick_split(&self, pieces: &[Piece]) -> usize {
+        let candidates: Vec<usize> = match self.options.split {
+            SplitHeuristic::First => return 0,
+            SplitHeuristic::RandomSample(samples) => {
+                // Xorshift, seeded so that building the same polygons gives the same tree
+                let mut state = 0x2545_f491_4f6c_dd1d ^ pieces.len() as u64;
+                (0..samples.max(1))
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % pieces.len() as u64) as usize
+                    })
+                    .collect()
+            }
+            _ => {
+                let step = pieces.len() / CANDIDATES + 1;
+                (0..pieces.len()).step_by(step).collect()
+            }
+        };
+
+        let balance_first = self.options.split == SplitHeuristic::Balance;
+
+        candidates
+            .into_iter()
+            .min_by_key(|&i| {
+                let (front, back, spanning) = count_sides(&pieces[i], pieces, &self.options);
+                let imbalance = front.max(back) - front.min(back);
+
+                if balance_first {
+                    (imbalance, spanning)
+                } else {
+                    (spanning, imbalance)
+                }
+            })
+            .unwrap_or(0)
+    }
+
+    /// Push the children of this node on `stack`, front last so it is visited first.
+    fn push_children_mut<'a>(&'a mut self, stack: &mut Vec<&'a mut BspNode>) {
+        let BspNode {
+            ref mut front,
+            ref mut back,
+            ..
+        } = *self;
+
+        if let Some(ref mut back) = *back {
+            stack.push(back);
+        }
+
+        if let Some(ref mut front) = *front {
+            stack.push(front);
+        }
+    }
+
+    /// Split by the plane of this node, decided exactly against the anchor in robust mode.
+    fn split_piece(
+        &self,
+        piece: &Piece,
+        coplane_front: &mut Vec<Piece>,
+        coplane_back: &mut Vec<Piece>,
+        front: &mut Vec<Piece>,
+        back: &mut Vec<Piece>,
+    ) {
+        if let Some(ref anchor) = self.anchor {
+            if self.options.robust {
+                return piece.split(anchor, coplane_front, coplane_back, front, back);
+            }
         }
 
-        // Recursively build the BSP tree
+        let (mut on_front, mut on_back) = (Vec::new(), Vec::new());
+        let (mut ahead, mut behind) = (Vec::new(), Vec::new());
+        self.plane.as_ref().unwrap().split_polygon_with(
+            &self.options,
+            &piece.polygon,
+            &mut on_front,
+            &mut on_back,
+            &mut ahead,
+            &mut behind,
+        );
+
+        coplane_front.extend(on_front.into_iter().map(Piece::new));
+        coplane_back.extend(on_back.into_iter().map(Piece::new));
+        front.extend(ahead.into_iter().map(Piece::new));
+        back.extend(behind.into_iter().map(Piece::new));
+    }
+}
 
-        if !front.is_empty() {
-            if self.front.is_none() {
-                self.front = Some(Box::new(BspNode::new(None)));
+impl Clone for BspNode {
+    /// Copies the tree without recursion, like all other traversals.
+    fn clone(&self) -> BspNode {
+        // Nodes in pre-order, with the positions of their children
+        let mut nodes: Vec<(&BspNode, Option<usize>, Option<usize>)> = vec![(self, None, None)];
+        let mut i = 0;
+        while i < nodes.len() {
+            let node = nodes[i].0;
+
+            if let Some(ref front) = node.front {
+                nodes[i].1 = Some(nodes.len());
+                nodes.push((front, None, None));
             }
 
-            self.front.as_mut().unwrap().build(front);
+            if let Some(ref back) = node.back {
+                nodes[i].2 = Some(nodes.len());
+                nodes.push((back, None, None));
+            }
+
+            i += 1;
+        }
+
+        // Children come after their parents, so copy from the end
+        let mut copies: Vec<Option<BspNode>> = (0..nodes.len()).map(|_| None).collect();
+        for (i, &(node, front, back)) in nodes.iter().enumerate().rev() {
+            let front = front.map(|c| Box::new(copies[c].take().unwrap()));
+            let back = back.map(|c| Box::new(copies[c].take().unwrap()));
+
+            copies[i] = Some(BspNode {
+                plane: node.plane.clone(),
+                front,
+                back,
+                polygons: node.polygons.clone(),
+                options: node.options,
+                anchor: node.anchor,
+                shapes: node.shapes.clone(),
+                splits: node.splits,
+            });
+        }
+
+        copies[0].take().unwrap()
+    }
+}
+
+impl Drop for BspNode {
+    /// Takes the subtrees apart one node at a time, dropping a deep tree recursively would
+    /// overflow the stack too.
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<BspNode>> = Vec::new();
+        stack.extend(self.front.take());
+        stack.extend(self.back.take());
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.front.take());
+            stack.extend(node.back.take());
         }
+    }
+}
 
-        if !back.is_empty() {
-            if self.back.is_none() {
-                self.back = Some(Box::new(BspNode::new(None)));
+/// Number of polygons entirely in front of the plane of `candidate`, entirely behind it, and
+/// spanning it. Decided exactly in robust mode, the same way the polygons are split by it.
+fn count_sides(candidate: &Piece, pieces: &[Piece], options: &CsgOptions) -> (usize, usize, usize) {
+    let plane = &candidate.polygon.plane;
+    let anchor = match candidate.shape {
+        Some(ref shape) if options.robust => Some(&shape.support),
+        _ => None,
+    };
+    let mut counts = (0, 0, 0);
+
+    for piece in pieces {
+        let exact = anchor.and_then(|anchor| piece.sides(anchor));
+        let (in_front, behind) = exact.unwrap_or_else(|| {
+            let (mut in_front, mut behind) = (false, false);
+            for v in &piece.polygon.vertices {
+                let t = plane.0.dot(v.position) - plane.1;
+                in_front |= t > options.epsilon;
+                behind |= t < -options.epsilon;
             }
+            (in_front, behind)
+        });
 
-            self.back.as_mut().unwrap().build(back);
+        match (in_front, behind) {
+            (true, false) => counts.0 += 1,
+            (false, true) => counts.1 += 1,
+            (true, true) => counts.2 += 1,
+            _ => (),
         }
     }
+
+    counts
+}
+
+/// `polygons` with their exact `shapes`, if they still match them.
+fn pieces(polygons: &[Polygon], shapes: &[Shape]) -> Vec<Piece> {
+    if shapes.len() == polygons.len() {
+        polygons
+            .iter()
+            .zip(shapes.iter())
+            .map(|(polygon, shape)| Piece {
+                polygon: polygon.clone(),
+                shape: Some(shape.clone()),
+            })
+            .collect()
+    } else {
+        pieces_of(polygons.to_vec())
+    }
+}
+
+/// Add the polygons of `pieces` to `polygons` and their outlines to `shapes`.
+fn store(pieces: Vec<Piece>, polygons: &mut Vec<Polygon>, shapes: &mut Vec<Shape>) {
+    for piece in pieces {
+        polygons.push(piece.polygon);
+        shapes.extend(piece.shape);
+    }
+}
+
+fn pieces_of(polygons: Vec<Polygon>) -> Vec<Piece> {
+    polygons.into_iter().map(Piece::new).collect()
+}
+
+fn polygons_of(pieces: Vec<Piece>) -> Vec<Polygon> {
+    pieces.into_iter().map(|piece| piece.polygon).collect()
 }
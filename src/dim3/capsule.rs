@@ -0,0 +1,67 @@
+use dim3::Csg;
+use dim3::{Polygon, Vector, Vertex};
+use {Unit, UNIT_PI};
+
+impl Csg {
+    /// Cylinder between two points with hemispherical ends.
+    ///
+    /// * `a` - Center of the first hemisphere
+    /// * `b` - Center of the second hemisphere
+    /// * `radius` - Radius of body and hemispheres
+    /// * `slices` - Number of subdivisions around the axis
+    /// * `stacks` - Number of subdivisions of each hemisphere, from pole to body
+    pub fn capsule(a: Vector, b: Vector, radius: Unit, slices: usize, stacks: usize) -> Csg {
+        let axis_z = (b - a).normalize();
+        let axis_x = axis_z.make_orthogonal();
+        let axis_y = axis_z.cross(axis_x);
+
+        let make_vertex = |center: Vector, theta: Unit, phi: Unit| {
+            let phi_sin = phi.sin();
+            let d = axis_x * (theta.cos() * phi_sin)
+                + axis_y * (theta.sin() * phi_sin)
+                + axis_z * phi.cos();
+
+            Vertex::new(center + d * radius, d)
+        };
+
+        // Rings from the pole at `b` to the pole at `a`, the body is the span between the two
+        // equators.
+        let delta_phi = UNIT_PI * 0.5 / (stacks as Unit);
+        let mut rings: Vec<(Vector, Unit)> = Vec::with_capacity(2 * stacks + 2);
+        for j in 0..=stacks {
+            rings.push((b, (j as Unit) * delta_phi));
+        }
+        for j in 0..=stacks {
+            rings.push((a, UNIT_PI * 0.5 + (j as Unit) * delta_phi));
+        }
+
+        let mut polys: Vec<Polygon> = Vec::new();
+        let delta_theta = UNIT_PI * 2.0 / (slices as Unit);
+        let last = rings.len() - 1;
+
+        for j in 0..last {
+            let (c0, phi0) = rings[j];
+            let (c1, phi1) = rings[j + 1];
+
+            for i in 0..slices {
+                let theta0 = (i as Unit) * delta_theta;
+                let theta1 = ((i + 1) % slices) as Unit * delta_theta;
+
+                let mut verts = vec![make_vertex(c0, theta0, phi0)];
+
+                // Collapse the quads touching a pole into triangles
+                verts.push(make_vertex(c1, theta0, phi1));
+                if j + 1 < last {
+                    verts.push(make_vertex(c1, theta1, phi1));
+                }
+                if j > 0 {
+                    verts.push(make_vertex(c0, theta1, phi0));
+                }
+
+                polys.push(Polygon::new(verts));
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+}
@@ -1,5 +1,7 @@
-use dim3::{BspNode, Polygon, Triangle, Vector, Vertex, Plane};
-use Unit;
+use dim3::exact::{self, Piece};
+use dim3::{Aabb, BspNode, Matrix4, Plane, Polygon, Quaternion, Triangle, Vector, Vertex};
+use std::slice;
+use {CsgOptions, Unit, UNIT_PI};
 
 #[derive(Clone)]
 pub struct Csg {
@@ -21,6 +23,11 @@ impl Csg {
         self.polygons.clone()
     }
 
+    /// Bounding box around all vertices, nothing for an empty `Csg`.
+    pub fn bounds(&self) -> Option<Aabb> {
+        Aabb::from_polygons(&self.polygons)
+    }
+
     pub fn refine(&self) -> Csg {
         let mut new_csg = Csg::new();
 
@@ -32,8 +39,8 @@ impl Csg {
             let mid_pos = poly
                 .vertices
                 .iter()
-                .fold(Vector(0f32, 0f32, 0f32), |acc, v| acc + v.position)
-                / (poly.vertices.len() as f32);
+                .fold(Vector(0., 0., 0.), |acc, v| acc + v.position)
+                / (poly.vertices.len() as Unit);
             let mid_nor = poly.vertices[0].normal;
 
             let mid_vert = Vertex::new(mid_pos, mid_nor);
@@ -114,6 +121,9 @@ impl Csg {
     }
 
     // Transformations
+
+    /// Apply `func` to every vertex. Vertex order is kept, so a `func` that mirrors space leaves
+    /// the polygons inside out.
     pub fn transform_vertices<F>(mut self, func: F) -> Csg
     where
         F: Fn(Vertex) -> Vertex,
@@ -133,52 +143,131 @@ impl Csg {
     }
 
     pub fn translate(self, v: Vector) -> Csg {
-        self.transform_vertices(|vert| Vertex {
-            position: vert.position + v,
-            ..vert
-        })
+        self.transform(&Matrix4::translation(v))
     }
 
+    /// Rotate around `axis` through origo, clockwise seen from the tip of `axis`.
     pub fn rotate(self, axis: Vector, angle_deg: Unit) -> Csg {
-        self.transform_vertices(|vert| Vertex {
-            position: vert.position.rotate(axis, angle_deg),
-            normal: if vert.normal.length() > 0. {
-                vert.normal.rotate(axis, angle_deg)
-            } else {
-                vert.normal
-            },
-            ..vert
-        })
+        self.transform(&Matrix4::rotation(axis, angle_deg))
     }
 
+    /// Rotate around origo by `q`.
+    pub fn rotate_quat(self, q: Quaternion) -> Csg {
+        self.transform(&q.to_matrix())
+    }
+
+    /// Scale around origo. Vertex normals follow the stretched surface. An odd number of negative
+    /// factors mirrors the solid, the winding is reversed so it still faces outwards.
     pub fn scale(self, v: Vector) -> Csg {
-        self.transform_vertices(|vert| Vertex {
-            position: Vector(
-                vert.position.0 * v.0,
-                vert.position.1 * v.1,
-                vert.position.2 * v.2,
-            ),
-            ..vert
-        })
+        self.transform(&Matrix4::scale(v))
+    }
+
+    /// Mirror through the plane through origo with normal `plane_normal`. The winding is reversed
+    /// so the solid still faces outwards.
+    pub fn mirror(self, plane_normal: Vector) -> Csg {
+        self.transform(&Matrix4::reflection(plane_normal))
+    }
+
+    /// Apply an affine transformation in a single pass. Vertex normals are transformed with the
+    /// inverse transpose of `m` and renormalized. Transformations that mirror space reverse the
+    /// winding so the solid still faces outwards.
+    pub fn transform(mut self, m: &Matrix4) -> Csg {
+        let normal_matrix = m.normal_matrix();
+        let mirrored = m.determinant3() < 0.;
+
+        for poly in &mut self.polygons {
+            for vert in &mut poly.vertices {
+                vert.position = m.transform_point(vert.position);
+
+                if vert.normal.length() > 0. {
+                    vert.normal = normal_matrix.transform_vector(vert.normal).normalize();
+                }
+            }
+
+            let normal = normal_matrix.transform_vector(poly.plane.0).normalize();
+            poly.plane = Plane(normal, normal.dot(poly.vertices[0].position));
+
+            if mirrored {
+                poly.vertices.reverse();
+            }
+        }
+        self
     }
 
     pub fn union(a: &Csg, b: &Csg) -> Csg {
-        let mut a = BspNode::new(Some(a.polygons.clone()));
-        let mut b = BspNode::new(Some(b.polygons.clone()));
+        Csg::union_with(a, b, &CsgOptions::default())
+    }
+
+    /// `union` with the tolerance in `options`.
+    pub fn union_with(a: &Csg, b: &Csg, options: &CsgOptions) -> Csg {
+        let overlap = match overlap_bounds(a, b, options) {
+            Some(overlap) => overlap,
+            None => {
+                let mut polygons = a.polygons.clone();
+                polygons.extend(b.polygons.iter().cloned());
+                return Csg::from_polygons(polygons);
+            }
+        };
+
+        // Polygons outside the overlap are outside the other solid and pass through
+        let (a_in, mut polygons) = split_by_bounds(&a.polygons, &overlap);
+        let (b_in, mut b_out) = split_by_bounds(&b.polygons, &overlap);
+        polygons.append(&mut b_out);
+
+        // Without faces of one solid in the overlap, the other is wholly inside or outside of it
+        if a_in.is_empty() || b_in.is_empty() {
+            if !lies_inside(b, &a_in) {
+                polygons.extend(a_in);
+            }
+            if !lies_inside(a, &b_in) {
+                polygons.extend(b_in);
+            }
+            return Csg::from_polygons(polygons);
+        }
+
+        let mut a = BspNode::with_options(Some(a_in), *options);
+        let mut b = BspNode::with_options(Some(b_in), *options);
 
         a.clip_to(&b);
         b.clip_to(&a);
         b.invert();
         b.clip_to(&a);
         b.invert();
-        a.build(b.all_polygons());
+        a.build_pieces(b.all_pieces());
 
-        Csg::from_polygons(a.all_polygons())
+        Csg::from_polygons(finish(polygons, a.all_pieces(), options))
     }
 
     pub fn subtract(a: &Csg, b: &Csg) -> Csg {
-        let mut a = BspNode::new(Some(a.polygons.clone()));
-        let mut b = BspNode::new(Some(b.polygons.clone()));
+        Csg::subtract_with(a, b, &CsgOptions::default())
+    }
+
+    /// `subtract` with the tolerance in `options`.
+    pub fn subtract_with(a: &Csg, b: &Csg, options: &CsgOptions) -> Csg {
+        let overlap = match overlap_bounds(a, b, options) {
+            Some(overlap) => overlap,
+            None => return a.clone(),
+        };
+
+        // Polygons of `a` outside the overlap are kept as they are, those of `b` are dropped
+        let (a_in, mut polygons) = split_by_bounds(&a.polygons, &overlap);
+        let (b_in, _) = split_by_bounds(&b.polygons, &overlap);
+
+        if a_in.is_empty() || b_in.is_empty() {
+            if !lies_inside(b, &a_in) {
+                polygons.extend(a_in);
+            }
+            if lies_inside(a, &b_in) {
+                polygons.extend(b_in.into_iter().map(|mut poly| {
+                    poly.flip();
+                    poly
+                }));
+            }
+            return Csg::from_polygons(polygons);
+        }
+
+        let mut a = BspNode::with_options(Some(a_in), *options);
+        let mut b = BspNode::with_options(Some(b_in), *options);
 
         a.invert();
         a.clip_to(&b);
@@ -186,24 +275,49 @@ impl Csg {
         b.invert();
         b.clip_to(&a);
         b.invert();
-        a.build(b.all_polygons());
+        a.build_pieces(b.all_pieces());
         a.invert();
 
-        Csg::from_polygons(a.all_polygons())
+        Csg::from_polygons(finish(polygons, a.all_pieces(), options))
     }
 
     pub fn intersect(a: &Csg, b: &Csg) -> Csg {
-        let mut a = BspNode::new(Some(a.polygons.clone()));
-        let mut b = BspNode::new(Some(b.polygons.clone()));
+        Csg::intersect_with(a, b, &CsgOptions::default())
+    }
+
+    /// `intersect` with the tolerance in `options`.
+    pub fn intersect_with(a: &Csg, b: &Csg, options: &CsgOptions) -> Csg {
+        let overlap = match overlap_bounds(a, b, options) {
+            Some(overlap) => overlap,
+            None => return Csg::new(),
+        };
+
+        // Nothing outside the overlap is kept
+        let (a_in, _) = split_by_bounds(&a.polygons, &overlap);
+        let (b_in, _) = split_by_bounds(&b.polygons, &overlap);
+
+        if a_in.is_empty() || b_in.is_empty() {
+            let mut polygons = Vec::new();
+            if lies_inside(b, &a_in) {
+                polygons.extend(a_in);
+            }
+            if lies_inside(a, &b_in) {
+                polygons.extend(b_in);
+            }
+            return Csg::from_polygons(polygons);
+        }
+
+        let mut a = BspNode::with_options(Some(a_in), *options);
+        let mut b = BspNode::with_options(Some(b_in), *options);
 
         a.invert();
         b.clip_to(&a);
         b.invert();
         a.clip_to(&b);
         b.clip_to(&a);
-        a.build(b.all_polygons());
+        a.build_pieces(b.all_pieces());
         a.invert();
-        Csg::from_polygons(a.all_polygons())
+        Csg::from_polygons(finish(Vec::new(), a.all_pieces(), options))
     }
 
     pub fn inverse(&self) -> Csg {
@@ -214,3 +328,66 @@ impl Csg {
         csg
     }
 }
+
+/// Polygons kept from outside the overlap followed by the result of the BSP trees. In robust mode
+/// the vertices of each lying on the edges of another are added to it, so the result is as closed
+/// as the solids were.
+fn finish(mut polygons: Vec<Polygon>, pieces: Vec<Piece>, options: &CsgOptions) -> Vec<Polygon> {
+    if options.robust {
+        let mut all: Vec<Piece> = polygons.into_iter().map(Piece::new).collect();
+        all.extend(pieces);
+        exact::seal(all)
+    } else {
+        polygons.extend(pieces.into_iter().map(|piece| piece.polygon));
+        polygons
+    }
+}
+
+/// Box shared by the bounds of `a` and `b` grown by twice the tolerance, so faces touching the
+/// other solid lie well inside it. Nothing if the bounds do not overlap.
+fn overlap_bounds(a: &Csg, b: &Csg, options: &CsgOptions) -> Option<Aabb> {
+    let margin = 2. * options.epsilon;
+    a.bounds()?
+        .expand(margin)
+        .intersection(&b.bounds()?.expand(margin))
+}
+
+/// Polygons reaching into `bounds` and the others. The BSP trees of a boolean only get the ones
+/// reaching into the overlap of both solids, the others are outside the other solid. Polygons are
+/// not cut at the box, so no new vertices end up on edges shared with the polygons passed by.
+fn split_by_bounds(polygons: &[Polygon], bounds: &Aabb) -> (Vec<Polygon>, Vec<Polygon>) {
+    polygons.iter().cloned().partition(|poly| {
+        Aabb::from_polygons(slice::from_ref(poly)).is_some_and(|bb| bb.overlaps(bounds))
+    })
+}
+
+/// True if `pieces` lie inside `solid`, judged by the center of the first of them. Only
+/// meaningful where `solid` has no faces, so all of them are on the same side.
+fn lies_inside(solid: &Csg, pieces: &[Polygon]) -> bool {
+    let poly = match pieces.first() {
+        Some(poly) => poly,
+        None => return false,
+    };
+    let center = poly
+        .vertices
+        .iter()
+        .fold(Vector(0., 0., 0.), |sum, v| sum + v.position)
+        / poly.vertices.len() as Unit;
+
+    // Seen from an inside point the faces cover every direction once, from an outside point they
+    // cancel out. Unlike counting the faces a ray crosses, adding up the solid angles they span
+    // does not depend on missing the edges between them.
+    let mut angle = 0.;
+    for face in solid.polygons.iter() {
+        let a = face.vertices[0].position - center;
+        for pair in face.vertices[1..].windows(2) {
+            let (b, c) = (pair[0].position - center, pair[1].position - center);
+            let (la, lb, lc) = (a.length(), b.length(), c.length());
+            let spanned = a.dot(b.cross(c));
+            let base = la * lb * lc + a.dot(b) * lc + a.dot(c) * lb + b.dot(c) * la;
+            angle += 2. * spanned.atan2(base);
+        }
+    }
+
+    angle.abs() > 2. * UNIT_PI
+}
@@ -0,0 +1,91 @@
+use dim3::Csg;
+use dim3::{Polygon, Vector, Vertex};
+use {Unit, EPSILON, UNIT_PI};
+
+impl Csg {
+    /// Cylinder between two points. Different radiuses at each end gives a cone or a frustum.
+    ///
+    /// * `start` - Center of the start cap
+    /// * `end` - Center of the end cap
+    /// * `radius_start` - Radius at `start`, zero makes a pointed tip
+    /// * `radius_end` - Radius at `end`, zero makes a pointed tip
+    /// * `slices` - Number of subdivisions around the axis
+    pub fn cylinder(
+        start: Vector,
+        end: Vector,
+        radius_start: Unit,
+        radius_end: Unit,
+        slices: usize,
+    ) -> Csg {
+        let ray = end - start;
+        let length = ray.length();
+        let axis_z = ray.normalize();
+        let axis_x = axis_z.make_orthogonal();
+        let axis_y = axis_z.cross(axis_x);
+
+        // Side normals lean towards the narrow end.
+        let slope = axis_z * (radius_start - radius_end);
+        let delta_theta = UNIT_PI * 2.0 / (slices as Unit);
+
+        let out = |i: usize| {
+            let theta = (i % slices) as Unit * delta_theta;
+            axis_x * theta.cos() + axis_y * theta.sin()
+        };
+        let side_vertex = |center: Vector, radius: Unit, i: usize| {
+            let o = out(i);
+            Vertex::new(center + o * radius, (o * length + slope).normalize())
+        };
+        let tip_vertex = |tip: Vector, i: usize| {
+            let o = (out(i) + out(i + 1)).normalize();
+            Vertex::new(tip, (o * length + slope).normalize())
+        };
+
+        let has_start_cap = radius_start > EPSILON;
+        let has_end_cap = radius_end > EPSILON;
+        let mut polys: Vec<Polygon> = Vec::new();
+
+        for i in 0..slices {
+            let j = i + 1;
+
+            // Start cap
+            if has_start_cap {
+                polys.push(Polygon::new(vec![
+                    Vertex::new(start, -axis_z),
+                    Vertex::new(start + out(j) * radius_start, -axis_z),
+                    Vertex::new(start + out(i) * radius_start, -axis_z),
+                ]));
+            }
+
+            // Side, a triangle if one of the ends is a tip
+            let mut side: Vec<Vertex> = Vec::with_capacity(4);
+            if has_start_cap {
+                side.push(side_vertex(start, radius_start, i));
+                side.push(side_vertex(start, radius_start, j));
+            } else {
+                side.push(tip_vertex(start, i));
+            }
+
+            if has_end_cap {
+                side.push(side_vertex(end, radius_end, j));
+                side.push(side_vertex(end, radius_end, i));
+            } else {
+                side.push(tip_vertex(end, i));
+            }
+
+            if side.len() >= 3 {
+                polys.push(Polygon::new(side));
+            }
+
+            // End cap
+            if has_end_cap {
+                polys.push(Polygon::new(vec![
+                    Vertex::new(end, axis_z),
+                    Vertex::new(end + out(i) * radius_end, axis_z),
+                    Vertex::new(end + out(j) * radius_end, axis_z),
+                ]));
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+}
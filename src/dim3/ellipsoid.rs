@@ -0,0 +1,52 @@
+use dim3::Csg;
+use dim3::{Polygon, Vector, Vertex};
+use {Unit, UNIT_PI};
+
+impl Csg {
+    /// Ellipsoid centered at origo.
+    ///
+    /// * `radii` - Radius along each axis
+    /// * `slices` - Number of subdivisions around the Z axis
+    /// * `stacks` - Number of subdivisions from pole to pole
+    pub fn ellipsoid(radii: Vector, slices: usize, stacks: usize) -> Csg {
+        fn make_vertex(radii: Vector, theta: Unit, phi: Unit) -> Vertex {
+            let phi_sin = phi.sin();
+            let d = Vector(theta.cos() * phi_sin, theta.sin() * phi_sin, phi.cos());
+            let position = Vector(d.0 * radii.0, d.1 * radii.1, d.2 * radii.2);
+
+            // Gradient of the implicit surface
+            let normal = Vector(d.0 / radii.0, d.1 / radii.1, d.2 / radii.2).normalize();
+
+            Vertex::new(position, normal)
+        }
+
+        let mut polys: Vec<Polygon> = Vec::new();
+        let delta_theta = UNIT_PI * 2.0 / (slices as Unit);
+        let delta_phi = UNIT_PI / (stacks as Unit);
+
+        for j in 0..stacks {
+            let phi0 = (j as Unit) * delta_phi;
+            let phi1 = ((j + 1) as Unit) * delta_phi;
+
+            for i in 0..slices {
+                let theta0 = (i as Unit) * delta_theta;
+                let theta1 = ((i + 1) % slices) as Unit * delta_theta;
+
+                let mut verts = vec![make_vertex(radii, theta0, phi0)];
+
+                // Collapse the quads touching a pole into triangles
+                verts.push(make_vertex(radii, theta0, phi1));
+                if j + 1 < stacks {
+                    verts.push(make_vertex(radii, theta1, phi1));
+                }
+                if j > 0 {
+                    verts.push(make_vertex(radii, theta1, phi0));
+                }
+
+                polys.push(Polygon::new(verts));
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+}
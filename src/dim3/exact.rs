@@ -0,0 +1,588 @@
+//! Polygons with exactly known outlines, split by BSP trees in robust mode. Vertices cut from a
+//! polygon are kept as the line and planes they were cut from instead of their rounded position,
+//! so the side of a plane they lie on is decided exactly by the predicates however often they are
+//! split again. Positions are rounded for the output polygons only.
+
+use dim3::{Aabb, Polygon, Vector, Vertex};
+use predicates::{floor_quotient, narrow, orient2d, orient3d, sign, wide, Arithmetic, Polynomial};
+use std::cmp::Ordering;
+use Unit;
+
+/// Three points spanning a plane, counter clockwise seen from its front.
+pub type Anchor = [Vector; 3];
+
+/// Polygon as kept in a BSP tree, with its exact outline in robust mode.
+#[derive(Clone)]
+pub struct Piece {
+    pub polygon: Polygon,
+    pub shape: Option<Shape>,
+}
+
+/// Exact outline of a convex polygon, lying on the plane of `support`.
+#[derive(Clone)]
+pub struct Shape {
+    pub support: Anchor,
+    points: Vec<Point>,
+    /// Edge from each point to the next.
+    edges: Vec<Edge>,
+}
+
+#[derive(Clone, Copy)]
+enum Point {
+    Given(Vector),
+    /// Where the line through two given points crosses a plane.
+    OnLine(Vector, Vector, Anchor),
+    /// Where three planes meet.
+    Corner(Anchor, Anchor, Anchor),
+}
+
+#[derive(Clone, Copy)]
+enum Edge {
+    /// Along the line through two given points.
+    Line(Vector, Vector),
+    /// Along the line a plane cuts from the support.
+    Cut(Anchor),
+}
+
+impl Piece {
+    pub fn new(polygon: Polygon) -> Piece {
+        Piece {
+            polygon,
+            shape: None,
+        }
+    }
+
+    /// Pieces with exact outlines covering this one. Those without one are triangulated, since
+    /// only triangles are sure to lie on their support whatever the rounding.
+    pub fn shaped(self) -> Vec<Piece> {
+        match self.shape {
+            Some(_) => vec![self],
+            None => triangulate(&self.polygon),
+        }
+    }
+
+    /// Whether some of the outline lies in front of the plane through `plane` and whether some
+    /// lies behind it, decided exactly. Nothing without an exact outline.
+    pub fn sides(&self, plane: &Anchor) -> Option<(bool, bool)> {
+        self.shape.as_ref().map(|shape| {
+            let sides = shape.points.iter().map(|p| p.side(plane));
+            (
+                sides.clone().any(|side| side == Ordering::Greater),
+                sides.clone().any(|side| side == Ordering::Less),
+            )
+        })
+    }
+
+    /// Sort by the plane through `plane` like `Plane::split_polygon`, deciding sides exactly.
+    /// Fragments keep the plane of this polygon.
+    pub fn split(
+        &self,
+        plane: &Anchor,
+        coplane_front: &mut Vec<Piece>,
+        coplane_back: &mut Vec<Piece>,
+        front: &mut Vec<Piece>,
+        back: &mut Vec<Piece>,
+    ) {
+        let shape = match self.shape {
+            Some(ref shape) => shape,
+            None => {
+                for piece in self.clone().shaped() {
+                    piece.split(plane, coplane_front, coplane_back, front, back);
+                }
+                return;
+            }
+        };
+
+        let sides: Vec<Ordering> = shape.points.iter().map(|p| p.side(plane)).collect();
+        let ahead = sides.contains(&Ordering::Greater);
+        let behind = sides.contains(&Ordering::Less);
+
+        if !ahead && !behind {
+            if facing(&shape.support, plane) {
+                coplane_front.push(self.clone());
+            } else {
+                coplane_back.push(self.clone());
+            }
+            return;
+        } else if !behind {
+            front.push(self.clone());
+            return;
+        } else if !ahead {
+            back.push(self.clone());
+            return;
+        }
+
+        let vertices = &self.polygon.vertices;
+        let n = vertices.len();
+        let mut f = Outline::default();
+        let mut b = Outline::default();
+
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let (si, sj) = (sides[i], sides[j]);
+            let edge = shape.edges[i];
+
+            // A point on the plane followed by one off it starts an edge along the plane
+            if si != Ordering::Less {
+                let on_plane = si == Ordering::Equal && sj == Ordering::Less;
+                f.push(vertices[i], shape.points[i], edge, on_plane, plane);
+            }
+
+            if si != Ordering::Greater {
+                let on_plane = si == Ordering::Equal && sj == Ordering::Greater;
+                b.push(vertices[i], shape.points[i], edge, on_plane, plane);
+            }
+
+            if si != Ordering::Equal && sj != Ordering::Equal && si != sj {
+                let point = match edge {
+                    Edge::Line(u, v) => Point::OnLine(u, v, *plane),
+                    Edge::Cut(cut) => Point::Corner(shape.support, cut, *plane),
+                };
+
+                let (di, dj) = (
+                    distance(plane, vertices[i].position),
+                    distance(plane, vertices[j].position),
+                );
+                let t = if di != dj { di / (di - dj) } else { 0.5 };
+                let mut vertex = vertices[i].interpolate(vertices[j], narrow(t.clamp(0., 1.)));
+                vertex.position = point.position();
+
+                f.push(vertex, point, edge, sj == Ordering::Less, plane);
+                b.push(vertex, point, edge, sj == Ordering::Greater, plane);
+            }
+        }
+
+        front.push(f.into_piece(&self.polygon, shape.support));
+        back.push(b.into_piece(&self.polygon, shape.support));
+    }
+}
+
+impl Shape {
+    /// Reverse the winding, like `Polygon::flip`.
+    pub fn flip(&mut self) {
+        self.support.swap(1, 2);
+        self.points.reverse();
+        // The edge after each point is now the one that was before it
+        self.edges.reverse();
+        self.edges.rotate_left(1);
+    }
+}
+
+/// Vertices, points and edges of a fragment being collected.
+#[derive(Default)]
+struct Outline {
+    vertices: Vec<Vertex>,
+    points: Vec<Point>,
+    edges: Vec<Edge>,
+}
+
+impl Outline {
+    /// Add a point, followed by `edge` or by an edge along `plane`.
+    fn push(&mut self, vertex: Vertex, point: Point, edge: Edge, on_plane: bool, plane: &Anchor) {
+        self.vertices.push(vertex);
+        self.points.push(point);
+        self.edges
+            .push(if on_plane { Edge::Cut(*plane) } else { edge });
+    }
+
+    fn into_piece(self, parent: &Polygon, support: Anchor) -> Piece {
+        Piece {
+            polygon: Polygon {
+                vertices: self.vertices,
+                plane: parent.plane.clone(),
+            },
+            shape: Some(Shape {
+                support,
+                points: self.points,
+                edges: self.edges,
+            }),
+        }
+    }
+}
+
+impl Point {
+    /// Side of the plane through `plane` this point lies on, `Equal` exactly on it.
+    fn side(&self, plane: &Anchor) -> Ordering {
+        if let Point::Given(p) = *self {
+            let d = orient3d(
+                coords(plane[0]),
+                coords(plane[1]),
+                coords(plane[2]),
+                coords(p),
+            );
+            return d.partial_cmp(&0.).unwrap_or(Ordering::Equal);
+        }
+
+        let side = sign(&Side(self, plane));
+        if sign(&Weight(self)) == Ordering::Less {
+            side.reverse()
+        } else {
+            side
+        }
+    }
+
+    /// True if this point lies exactly on the line along `edge` of a polygon on `support`.
+    fn on_edge(&self, edge: &Edge, support: Option<&Anchor>) -> bool {
+        match *edge {
+            Edge::Line(u, v) => {
+                (0..3).all(|axis| sign(&Collinear(self, u, v, axis)) == Ordering::Equal)
+            }
+            Edge::Cut(ref cut) => support.is_some_and(|support| {
+                self.side(support) == Ordering::Equal && self.side(cut) == Ordering::Equal
+            }),
+        }
+    }
+
+    /// Position rounded down to `Unit` from the exact one, so fragments sharing a point get the
+    /// same position for it whichever planes they cut it with.
+    fn position(&self) -> Vector {
+        match *self {
+            Point::Given(p) => p,
+            _ => {
+                let coordinate = |axis| floor_quotient(&Coordinate(self, axis), &Weight(self));
+                Vector(coordinate(0), coordinate(1), coordinate(2))
+            }
+        }
+    }
+
+    /// Coordinates and a weight dividing them into the position, exactly in `T`.
+    fn homogeneous<T: Arithmetic>(&self) -> ([T; 3], T) {
+        match *self {
+            Point::Given(p) => (lift(p), T::from_f64(1.)),
+            Point::OnLine(u, v, ref cut) => {
+                let (from, to) = (value::<T>(cut, u), value::<T>(cut, v));
+                let (u, v) = (lift::<T>(u), lift::<T>(v));
+                let coordinate =
+                    |axis: usize| from.clone() * v[axis].clone() - to.clone() * u[axis].clone();
+                (
+                    [coordinate(0), coordinate(1), coordinate(2)],
+                    from.clone() - to.clone(),
+                )
+            }
+            Point::Corner(ref p, ref q, ref r) => {
+                let (n0, d0) = plane_of::<T>(p);
+                let (n1, d1) = plane_of::<T>(q);
+                let (n2, d2) = plane_of::<T>(r);
+                let (c12, c20, c01) = (cross(&n1, &n2), cross(&n2, &n0), cross(&n0, &n1));
+                let coordinate = |axis: usize| {
+                    d0.clone() * c12[axis].clone()
+                        + d1.clone() * c20[axis].clone()
+                        + d2.clone() * c01[axis].clone()
+                };
+                (
+                    [coordinate(0), coordinate(1), coordinate(2)],
+                    dot(&n0, &c12),
+                )
+            }
+        }
+    }
+}
+
+/// Polygons of `pieces` with the vertices of the others lying on their edges added, so no polygon
+/// ends in the middle of an edge of its neighbor. Together with the consistent rounding this makes
+/// the output of a boolean on closed solids closed again.
+pub fn seal(pieces: Vec<Piece>) -> Vec<Polygon> {
+    // Every vertex with its exact point, sorted along X for finding the ones near an edge
+    let mut pool: Vec<(Vector, Point)> = Vec::new();
+    for piece in &pieces {
+        let vertices = piece.polygon.vertices.iter().map(|v| v.position);
+        match piece.shape {
+            Some(ref shape) => pool.extend(vertices.zip(shape.points.iter().cloned())),
+            None => pool.extend(vertices.map(|p| (p, Point::Given(p)))),
+        }
+    }
+    pool.sort_by(|a, b| key(a.0).partial_cmp(&key(b.0)).unwrap_or(Ordering::Equal));
+
+    pieces
+        .into_iter()
+        .map(|piece| {
+            let polygon = piece.polygon;
+            let n = polygon.vertices.len();
+            let mut vertices = Vec::with_capacity(n);
+
+            for i in 0..n {
+                let (a, b) = (polygon.vertices[i], polygon.vertices[(i + 1) % n]);
+                let (edge, support) = match piece.shape {
+                    Some(ref shape) => (shape.edges[i], Some(&shape.support)),
+                    None => (Edge::Line(a.position, b.position), None),
+                };
+                vertices.push(a);
+
+                let bounds = Aabb {
+                    min: a.position,
+                    max: a.position,
+                }
+                .union(&Aabb {
+                    min: b.position,
+                    max: b.position,
+                });
+                let along = b.position - a.position;
+
+                // Rounding keeps the order of coordinates, so points between the ends before
+                // rounding still are after it, if maybe level with one of them
+                let start = pool.partition_point(|&(p, _)| p.0 < bounds.min.0);
+                let mut between: Vec<(Unit, Vector)> = pool[start..]
+                    .iter()
+                    .take_while(|&&(p, _)| p.0 <= bounds.max.0)
+                    .filter(|&&(p, ref point)| {
+                        bounds.contains(p)
+                            && key(p) != key(a.position)
+                            && key(p) != key(b.position)
+                            && point.on_edge(&edge, support)
+                    })
+                    .map(|&(p, _)| ((p - a.position).dot(along) / along.dot(along), p))
+                    .collect();
+
+                // A position may be shared by several points, of which any may be on the edge
+                between.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+                between.dedup_by(|a, b| key(a.1) == key(b.1));
+                vertices.extend(between.into_iter().map(|(t, p)| {
+                    let mut vertex = a.interpolate(b, t);
+                    vertex.position = p;
+                    vertex
+                }));
+            }
+
+            Polygon {
+                vertices,
+                plane: polygon.plane,
+            }
+        })
+        .collect()
+}
+
+/// Exact triangles covering `poly`, cut off as ears so rounded outlines that are no longer convex
+/// still do not fold over. Ears are tried from the lowest vertex up, so faces with the same
+/// corners, whichever way they wind, are cut along the same diagonals and lie exactly on each
+/// other. Triangles without area are left out.
+fn triangulate(poly: &Polygon) -> Vec<Piece> {
+    let v = &poly.vertices;
+
+    // Turns are judged seen along the axis the polygon faces most
+    let normal = coords(poly.plane.0);
+    let axis = (1..3).fold(0, |axis, i| {
+        if normal[i].abs() > normal[axis].abs() {
+            i
+        } else {
+            axis
+        }
+    });
+    let project = |p: Vector| {
+        let p = coords(p);
+        [p[(axis + 1) % 3], p[(axis + 2) % 3]]
+    };
+    let turn = |a: usize, b: usize, c: usize| {
+        let t = orient2d(
+            project(v[a].position),
+            project(v[b].position),
+            project(v[c].position),
+        );
+        if normal[axis] < 0. {
+            -t
+        } else {
+            t
+        }
+    };
+
+    let mut outline: Vec<usize> = (0..v.len()).collect();
+    let mut triangles = Vec::new();
+    while outline.len() > 3 {
+        let m = outline.len();
+        let corners = |k: usize| [outline[(k + m - 1) % m], outline[k], outline[(k + 1) % m]];
+        let mut order: Vec<usize> = (0..m).collect();
+        order.sort_by(|&i, &j| {
+            key(v[outline[i]].position)
+                .partial_cmp(&key(v[outline[j]].position))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let ear = order.into_iter().find(|&k| {
+            let [a, b, c] = corners(k);
+            let corner = |q: usize| {
+                [a, b, c]
+                    .iter()
+                    .any(|&i| key(v[i].position) == key(v[q].position))
+            };
+            turn(a, b, c) >= 0.
+                && !outline.iter().any(|&q| {
+                    !corner(q) && turn(a, b, q) >= 0. && turn(b, c, q) >= 0. && turn(c, a, q) >= 0.
+                })
+        });
+
+        match ear {
+            Some(k) => {
+                triangles.push(corners(k));
+                outline.remove(k);
+            }
+            // Folded over beyond repair, fan the rest
+            None => break,
+        }
+    }
+    let rest = outline.len();
+    triangles.extend((1..rest.max(2) - 1).map(|i| [outline[0], outline[i], outline[i + 1]]));
+
+    triangles
+        .into_iter()
+        .map(|corners| [v[corners[0]], v[corners[1]], v[corners[2]]])
+        .filter(|corners| {
+            let (a, b, c) = (
+                coords(corners[0].position),
+                coords(corners[1].position),
+                coords(corners[2].position),
+            );
+            let flat =
+                |i: usize, j: usize| orient2d([a[i], a[j]], [b[i], b[j]], [c[i], c[j]]) == 0.;
+            !(flat(0, 1) && flat(1, 2) && flat(2, 0))
+        })
+        .map(|corners| {
+            let p: Vec<Vector> = corners.iter().map(|v| v.position).collect();
+            Piece {
+                polygon: Polygon {
+                    vertices: corners.to_vec(),
+                    plane: poly.plane.clone(),
+                },
+                shape: Some(Shape {
+                    support: [p[0], p[1], p[2]],
+                    points: p.iter().map(|&p| Point::Given(p)).collect(),
+                    edges: (0..3).map(|i| Edge::Line(p[i], p[(i + 1) % 3])).collect(),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// True if the planes through `a` and `b`, known to be the same, face the same way.
+fn facing(a: &Anchor, b: &Anchor) -> bool {
+    let (na, _) = plane_of::<f64>(a);
+    let (nb, _) = plane_of::<f64>(b);
+    dot(&na, &nb) > 0.
+}
+
+/// Distance from the plane through `plane`, scaled by the area of its anchor. Only used for
+/// interpolating, sides are decided by `Point::side`.
+fn distance(plane: &Anchor, p: Vector) -> f64 {
+    orient3d(
+        coords(plane[0]),
+        coords(plane[1]),
+        coords(plane[2]),
+        coords(p),
+    )
+}
+
+/// Coordinates of a position, for comparing them.
+fn key(p: Vector) -> (Unit, Unit, Unit) {
+    (p.0, p.1, p.2)
+}
+
+fn coords(p: Vector) -> [f64; 3] {
+    [wide(p.0), wide(p.1), wide(p.2)]
+}
+
+fn lift<T: Arithmetic>(p: Vector) -> [T; 3] {
+    [
+        T::from_f64(wide(p.0)),
+        T::from_f64(wide(p.1)),
+        T::from_f64(wide(p.2)),
+    ]
+}
+
+fn difference<T: Arithmetic>(a: &[T; 3], b: &[T; 3]) -> [T; 3] {
+    [
+        a[0].clone() - b[0].clone(),
+        a[1].clone() - b[1].clone(),
+        a[2].clone() - b[2].clone(),
+    ]
+}
+
+fn cross<T: Arithmetic>(a: &[T; 3], b: &[T; 3]) -> [T; 3] {
+    [
+        a[1].clone() * b[2].clone() - a[2].clone() * b[1].clone(),
+        a[2].clone() * b[0].clone() - a[0].clone() * b[2].clone(),
+        a[0].clone() * b[1].clone() - a[1].clone() * b[0].clone(),
+    ]
+}
+
+fn dot<T: Arithmetic>(a: &[T; 3], b: &[T; 3]) -> T {
+    a[0].clone() * b[0].clone() + a[1].clone() * b[1].clone() + a[2].clone() * b[2].clone()
+}
+
+/// Normal and offset of the plane through `anchor`, not normalized. Its value `n·p - d` at a
+/// point has the sign of `orient3d` there.
+fn plane_of<T: Arithmetic>(anchor: &Anchor) -> ([T; 3], T) {
+    let a = lift(anchor[0]);
+    let normal = cross(
+        &difference(&lift(anchor[1]), &a),
+        &difference(&lift(anchor[2]), &a),
+    );
+    let offset = dot(&normal, &a);
+    (normal, offset)
+}
+
+/// Value `orient3d` takes for the plane through `plane` at `p`.
+fn value<T: Arithmetic>(plane: &Anchor, p: Vector) -> T {
+    let a = lift::<T>(plane[0]);
+    let normal = cross(
+        &difference(&lift(plane[1]), &a),
+        &difference(&lift(plane[2]), &a),
+    );
+    dot(&normal, &difference(&lift(p), &a))
+}
+
+/// Value of the plane like `value`, at the homogeneous coordinates of a point. Its sign is the
+/// side the point lies on if the weight is positive.
+struct Side<'a>(&'a Point, &'a Anchor);
+
+impl<'a> Polynomial for Side<'a> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        let (coordinates, weight) = self.0.homogeneous::<T>();
+        let a = lift::<T>(self.1[0]);
+        let normal = cross(
+            &difference(&lift(self.1[1]), &a),
+            &difference(&lift(self.1[2]), &a),
+        );
+        let scaled = [
+            weight.clone() * a[0].clone(),
+            weight.clone() * a[1].clone(),
+            weight * a[2].clone(),
+        ];
+        dot(&normal, &difference(&coordinates, &scaled))
+    }
+}
+
+struct Weight<'a>(&'a Point);
+
+impl<'a> Polynomial for Weight<'a> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        self.0.homogeneous::<T>().1
+    }
+}
+
+struct Coordinate<'a>(&'a Point, usize);
+
+impl<'a> Polynomial for Coordinate<'a> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        let (coordinates, _) = self.0.homogeneous::<T>();
+        coordinates[self.1].clone()
+    }
+}
+
+/// Component along `axis` of the cross product of the line from `u` to `v` and the point seen
+/// from `u`, all zero when the point lies on the line.
+struct Collinear<'a>(&'a Point, Vector, Vector, usize);
+
+impl<'a> Polynomial for Collinear<'a> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        let (coordinates, weight) = self.0.homogeneous::<T>();
+        let u = lift::<T>(self.1);
+        let scaled = [
+            weight.clone() * u[0].clone(),
+            weight.clone() * u[1].clone(),
+            weight * u[2].clone(),
+        ];
+        let product = cross(
+            &difference(&lift(self.2), &u),
+            &difference(&coordinates, &scaled),
+        );
+        product[self.3].clone()
+    }
+}
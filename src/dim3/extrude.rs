@@ -0,0 +1,410 @@
+use dim2;
+use dim2::{signed_area, triangulate, Point};
+use dim3::Csg;
+use dim3::{Matrix4, Polygon, Vector};
+use {Unit, EPSILON, UNIT_PI};
+
+impl Csg {
+    /// Extrude the filled regions of a 2D shape along the Z axis, like OpenSCAD's
+    /// `linear_extrude`.
+    ///
+    /// * `shape` - Closed 2D shape in the XY plane
+    /// * `height` - Extent along the Z axis
+    /// * `twist_deg` - Clockwise rotation of the top relative to the bottom, seen from above
+    /// * `slices` - Number of layers along the Z axis, more give smoother twists
+    /// * `end_scale` - Scale of the top relative to the bottom along X and Y
+    /// * `center` - Center along the Z axis, otherwise it starts at origo
+    pub fn linear_extrude(
+        shape: &dim2::Csg,
+        height: Unit,
+        twist_deg: Unit,
+        slices: usize,
+        end_scale: Point,
+        center: bool,
+    ) -> Csg {
+        let slices = slices.max(1);
+        let bottom = if center { -height * 0.5 } else { 0. };
+
+        let level = |p: Point, k: usize| {
+            let t = (k as Unit) / (slices as Unit);
+            let x = p.0 * (1. + (end_scale.0 - 1.) * t);
+            let y = p.1 * (1. + (end_scale.1 - 1.) * t);
+            let angle = -UNIT_PI * twist_deg * t / 180.;
+            let (s, c) = (angle.sin(), angle.cos());
+
+            Vector(c * x - s * y, s * x + c * y, bottom + height * t)
+        };
+
+        // Side walls stay flat only when every layer is a uniformly scaled copy of the first.
+        let flat_walls = twist_deg == 0. && end_scale.0 == end_scale.1;
+
+        let mut polys: Vec<Polygon> = Vec::new();
+
+        for region in shape.to_regions() {
+            let points = region.points();
+
+            for tri in region.triangulate() {
+                let [a, b, c] = [points[tri[0]], points[tri[1]], points[tri[2]]];
+                polys.extend(Polygon::flat(&[level(c, 0), level(b, 0), level(a, 0)]));
+                polys.extend(Polygon::flat(&[
+                    level(a, slices),
+                    level(b, slices),
+                    level(c, slices),
+                ]));
+            }
+
+            for outline in Some(&region.outline).into_iter().chain(region.holes.iter()) {
+                let len = outline.len();
+
+                for i in 0..len {
+                    let p0 = outline[i];
+                    let p1 = outline[(i + 1) % len];
+
+                    for k in 0..slices {
+                        let b0 = level(p0, k);
+                        let b1 = level(p1, k);
+                        let t0 = level(p0, k + 1);
+                        let t1 = level(p1, k + 1);
+
+                        if flat_walls {
+                            polys.extend(Polygon::flat(&[b0, b1, t1, t0]));
+                        } else {
+                            polys.extend(Polygon::flat(&[b0, b1, t1]));
+                            polys.extend(Polygon::flat(&[b0, t1, t0]));
+                        }
+                    }
+                }
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+
+    /// Revolve the filled regions of a 2D shape around the Z axis, like OpenSCAD's
+    /// `rotate_extrude`. The X axis of the shape becomes the distance from the Z axis and the Y
+    /// axis of the shape becomes Z. Parts of the shape touching the axis close up without
+    /// degenerate polygons.
+    ///
+    /// * `shape` - Closed 2D shape, must not cross to negative X
+    /// * `angle_deg` - Counter clockwise sweep seen from above, negative sweeps clockwise. 360 or
+    ///   more either way makes a full turn
+    /// * `segments` - Number of steps to sweep `angle_deg`
+    pub fn rotate_extrude(shape: &dim2::Csg, angle_deg: Unit, segments: usize) -> Csg {
+        let segments = segments.max(1);
+        let full_turn = angle_deg.abs() >= 360.;
+        let angle_deg = angle_deg.clamp(-360., 360.);
+
+        let step = |p: Point, k: usize| {
+            let k = if full_turn { k % segments } else { k };
+            let angle = UNIT_PI * angle_deg * (k as Unit) / (segments as Unit) / 180.;
+
+            Vector(p.0 * angle.cos(), p.0 * angle.sin(), p.1)
+        };
+
+        let mut polys: Vec<Polygon> = Vec::new();
+
+        for region in shape.to_regions() {
+            if !full_turn {
+                let points = region.points();
+
+                for tri in region.triangulate() {
+                    let [a, b, c] = [points[tri[0]], points[tri[1]], points[tri[2]]];
+                    polys.extend(Polygon::flat(&[step(a, 0), step(b, 0), step(c, 0)]));
+                    polys.extend(Polygon::flat(&[
+                        step(c, segments),
+                        step(b, segments),
+                        step(a, segments),
+                    ]));
+                }
+            }
+
+            for outline in Some(&region.outline).into_iter().chain(region.holes.iter()) {
+                let len = outline.len();
+
+                for i in 0..len {
+                    let p0 = outline[i];
+                    let p1 = outline[(i + 1) % len];
+
+                    for k in 0..segments {
+                        polys.extend(Polygon::flat(&[
+                            step(p0, k),
+                            step(p0, k + 1),
+                            step(p1, k + 1),
+                            step(p1, k),
+                        ]));
+                    }
+                }
+            }
+        }
+
+        // Polygons are wound for a counter clockwise sweep, a clockwise one mirrors them
+        if angle_deg < 0. {
+            for poly in &mut polys {
+                poly.flip();
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+
+    /// Sweep the filled regions of a 2D shape along a path. The shape is kept perpendicular to
+    /// the path using rotation minimizing frames, so it does not twist around the path more than
+    /// the path itself requires. The X and Y axes of the shape start out as close to the world X
+    /// and Y axes as the start direction of the path allows.
+    ///
+    /// * `shape` - Closed 2D shape, placed with origo on the path
+    /// * `path` - Open polyline of at least two points, repeated points are skipped
+    pub fn sweep(shape: &dim2::Csg, path: &[Vector]) -> Csg {
+        let mut points: Vec<Vector> = Vec::with_capacity(path.len());
+        for &p in path {
+            match points.last() {
+                Some(&prev) if (p - prev).length() <= EPSILON => (),
+                _ => points.push(p),
+            }
+        }
+
+        let path = &points[..];
+        if path.len() < 2 {
+            return Csg::new();
+        }
+
+        let last = path.len() - 1;
+        let tangents: Vec<Vector> = (0..path.len())
+            .map(|i| (path[(i + 1).min(last)] - path[i.saturating_sub(1)]).normalize())
+            .collect();
+
+        // Start frame, then propagate it with the double reflection method.
+        let start = tangents[0];
+        let mut right = Vector(1., 0., 0.) - start * start.0;
+        if right.length() <= EPSILON {
+            right = Vector(0., 1., 0.) - start * start.1;
+        }
+
+        let mut frames: Vec<(Vector, Vector)> = Vec::with_capacity(path.len());
+        frames.push((right.normalize(), start.cross(right.normalize())));
+
+        for i in 0..last {
+            let r = frames[i].0;
+            let v1 = path[i + 1] - path[i];
+            let c1 = v1.dot(v1);
+            let r_l = r - v1 * (2. / c1 * v1.dot(r));
+            let t_l = tangents[i] - v1 * (2. / c1 * v1.dot(tangents[i]));
+
+            let v2 = tangents[i + 1] - t_l;
+            let c2 = v2.dot(v2);
+            let next = if c2 <= EPSILON * EPSILON {
+                r_l
+            } else {
+                r_l - v2 * (2. / c2 * v2.dot(r_l))
+            }
+            .normalize();
+
+            frames.push((next, tangents[i + 1].cross(next)));
+        }
+
+        let place = |p: Point, i: usize| {
+            let (r, s) = frames[i];
+            path[i] + r * p.0 + s * p.1
+        };
+
+        let mut polys: Vec<Polygon> = Vec::new();
+
+        for region in shape.to_regions() {
+            let points = region.points();
+
+            for tri in region.triangulate() {
+                let [a, b, c] = [points[tri[0]], points[tri[1]], points[tri[2]]];
+                polys.extend(Polygon::flat(&[place(c, 0), place(b, 0), place(a, 0)]));
+                polys.extend(Polygon::flat(&[
+                    place(a, last),
+                    place(b, last),
+                    place(c, last),
+                ]));
+            }
+
+            for outline in Some(&region.outline).into_iter().chain(region.holes.iter()) {
+                let len = outline.len();
+
+                for i in 0..len {
+                    let p0 = outline[i];
+                    let p1 = outline[(i + 1) % len];
+
+                    for k in 0..last {
+                        let b0 = place(p0, k);
+                        let b1 = place(p1, k);
+                        let t0 = place(p0, k + 1);
+                        let t1 = place(p1, k + 1);
+
+                        polys.extend(Polygon::flat(&[b0, b1, t1]));
+                        polys.extend(Polygon::flat(&[b0, t1, t0]));
+                    }
+                }
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+
+    /// Skin a solid through a sequence of 2D sections placed in 3D. Sections with different
+    /// numbers of points are resampled by perimeter length, starting where each outline crosses
+    /// the X axis through its center, so round and square sections connect without twisting.
+    /// Only the outline of the largest region of each section is used.
+    ///
+    /// * `sections` - 2D shapes in order, each with the transformation placing it in 3D
+    pub fn loft(sections: &[(dim2::Csg, Matrix4)]) -> Csg {
+        let outlines: Vec<Vec<Point>> = sections
+            .iter()
+            .filter_map(|(shape, _)| aligned_outline(shape))
+            .collect();
+
+        if outlines.len() < 2 || outlines.len() != sections.len() {
+            return Csg::new();
+        }
+
+        // Sample every section at the corners of all sections
+        let mut params: Vec<Unit> = outlines.iter().flat_map(|o| perimeter_params(o)).collect();
+        params.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        params.dedup_by(|a, b| (*a - *b).abs() <= EPSILON);
+
+        let rings: Vec<Vec<Vector>> = outlines
+            .iter()
+            .zip(sections.iter())
+            .map(|(outline, (_, place))| {
+                let outline_params = perimeter_params(outline);
+                params
+                    .iter()
+                    .map(|&t| {
+                        let p = sample_outline(outline, &outline_params, t);
+                        place.transform_point(Vector(p.0, p.1, 0.))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut polys: Vec<Polygon> = Vec::new();
+        let len = params.len();
+
+        for pair in rings.windows(2) {
+            let (bottom, top) = (&pair[0], &pair[1]);
+
+            for i in 0..len {
+                let j = (i + 1) % len;
+                polys.extend(Polygon::flat(&[bottom[i], bottom[j], top[j]]));
+                polys.extend(Polygon::flat(&[bottom[i], top[j], top[i]]));
+            }
+        }
+
+        // Caps, wound for sections stacked along their own Z axis
+        for &(cap, reverse) in &[(0, true), (rings.len() - 1, false)] {
+            let outline = &outlines[cap];
+            let outline_params = perimeter_params(outline);
+            let flat: Vec<Point> = params
+                .iter()
+                .map(|&t| sample_outline(outline, &outline_params, t))
+                .collect();
+
+            for tri in triangulate(&flat) {
+                let corners = [rings[cap][tri[0]], rings[cap][tri[1]], rings[cap][tri[2]]];
+                if reverse {
+                    polys.extend(Polygon::flat(&[corners[2], corners[1], corners[0]]));
+                } else {
+                    polys.extend(Polygon::flat(&corners));
+                }
+            }
+        }
+
+        let csg = Csg::from_polygons(polys);
+
+        // Sections stacked against their own Z axis turn the solid inside out
+        let mut volume = 0.;
+        csg.iter_triangles(|tri| {
+            let [a, b, c] = tri.positions;
+            volume += a.dot(b.cross(c));
+        });
+
+        if volume < 0. {
+            csg.inverse()
+        } else {
+            csg
+        }
+    }
+}
+
+/// Counter clockwise outline of the largest region of `shape`, starting where it crosses the X
+/// axis through its center.
+fn aligned_outline(shape: &dim2::Csg) -> Option<Vec<Point>> {
+    let outline = shape
+        .to_regions()
+        .into_iter()
+        .map(|r| r.outline)
+        .max_by(|a, b| signed_area(a).partial_cmp(&signed_area(b)).unwrap())?;
+
+    let len = outline.len();
+    let center = outline.iter().fold(Point(0., 0.), |acc, &p| acc + p) / (len as Unit);
+
+    // Closest crossing of the ray from the center along X
+    let mut start: Option<(Unit, usize, Point)> = None;
+    for i in 0..len {
+        let a = outline[i] - center;
+        let b = outline[(i + 1) % len] - center;
+
+        if (a.1 <= 0. && b.1 > 0.) || (a.1 > 0. && b.1 <= 0.) {
+            let t = -a.1 / (b.1 - a.1);
+            let x = a.0 + (b.0 - a.0) * t;
+            let closer = match start {
+                Some((best, _, _)) => x < best,
+                None => true,
+            };
+
+            if x > 0. && closer {
+                start = Some((x, i, Point(center.0 + x, center.1)));
+            }
+        }
+    }
+
+    let mut aligned: Vec<Point> = Vec::with_capacity(len + 1);
+    match start {
+        Some((_, i, crossing)) => {
+            aligned.push(crossing);
+            for k in 1..=len {
+                let p = outline[(i + k) % len];
+                if (p - crossing).length() > EPSILON {
+                    aligned.push(p);
+                }
+            }
+        }
+        None => aligned = outline,
+    }
+
+    Some(aligned)
+}
+
+/// Position of every point along the perimeter of a closed outline, from 0 to below 1.
+fn perimeter_params(outline: &[Point]) -> Vec<Unit> {
+    let len = outline.len();
+    let mut params: Vec<Unit> = Vec::with_capacity(len);
+    let mut total = 0.;
+
+    for i in 0..len {
+        params.push(total);
+        total += (outline[(i + 1) % len] - outline[i]).length();
+    }
+
+    params.iter().map(|p| p / total).collect()
+}
+
+/// Point at perimeter position `t` of a closed outline.
+fn sample_outline(outline: &[Point], params: &[Unit], t: Unit) -> Point {
+    let len = outline.len();
+    let i = params.iter().rposition(|&p| p <= t).unwrap_or(0);
+
+    let end = if i + 1 < len { params[i + 1] } else { 1. };
+    let span = end - params[i];
+    let local = if span > 0. {
+        (t - params[i]) / span
+    } else {
+        0.
+    };
+
+    outline[i].interpolate(&outline[(i + 1) % len], local)
+}
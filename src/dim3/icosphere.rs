@@ -0,0 +1,91 @@
+use dim3::Csg;
+use dim3::{Polygon, Vector, Vertex};
+use Unit;
+
+/// Corners of an icosahedron with circumradius 1, faces wound counter clockwise seen from the
+/// outside.
+pub(crate) fn icosahedron_triangles() -> Vec<[Vector; 3]> {
+    let t: Unit = (1.0 + (5.0 as Unit).sqrt()) / 2.0;
+
+    let points: Vec<Vector> = [
+        Vector(-1., t, 0.),
+        Vector(1., t, 0.),
+        Vector(-1., -t, 0.),
+        Vector(1., -t, 0.),
+        Vector(0., -1., t),
+        Vector(0., 1., t),
+        Vector(0., -1., -t),
+        Vector(0., 1., -t),
+        Vector(t, 0., -1.),
+        Vector(t, 0., 1.),
+        Vector(-t, 0., -1.),
+        Vector(-t, 0., 1.),
+    ]
+    .iter()
+    .map(|p| p.normalize())
+    .collect();
+
+    [
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ]
+    .iter()
+    .map(|f| [points[f[0]], points[f[1]], points[f[2]]])
+    .collect()
+}
+
+impl Csg {
+    /// Sphere made from a subdivided icosahedron. All triangles are of similar size and there are
+    /// no poles.
+    ///
+    /// * `radius` - Radius of sphere
+    /// * `subdivisions` - Number of times each triangle is split in four, zero gives an
+    ///   icosahedron
+    pub fn icosphere(radius: Unit, subdivisions: usize) -> Csg {
+        let mut triangles = icosahedron_triangles();
+
+        for _ in 0..subdivisions {
+            let mut next: Vec<[Vector; 3]> = Vec::with_capacity(triangles.len() * 4);
+
+            for &[a, b, c] in &triangles {
+                // Midpoints are computed from the same pair of corners on both sides of an edge,
+                // so neighbouring triangles share them exactly.
+                let ab = (a + b).normalize();
+                let bc = (b + c).normalize();
+                let ca = (c + a).normalize();
+
+                next.push([a, ab, ca]);
+                next.push([ab, b, bc]);
+                next.push([ca, bc, c]);
+                next.push([ab, bc, ca]);
+            }
+
+            triangles = next;
+        }
+
+        Csg::from_polygons(
+            triangles
+                .iter()
+                .map(|tri| Polygon::new(tri.iter().map(|&d| Vertex::new(d * radius, d)).collect()))
+                .collect(),
+        )
+    }
+}
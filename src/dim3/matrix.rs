@@ -0,0 +1,183 @@
+use dim3::Vector;
+use std::ops::Mul;
+use {Unit, UNIT_PI};
+
+/// Affine transformation in 3D space, stored as rows. Points are column vectors, so in `a * b`
+/// the transformation `b` is applied first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix4(pub [[Unit; 4]; 4]);
+
+impl Matrix4 {
+    pub fn identity() -> Matrix4 {
+        Matrix4([
+            [1., 0., 0., 0.],
+            [0., 1., 0., 0.],
+            [0., 0., 1., 0.],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Move by `v`.
+    pub fn translation(v: Vector) -> Matrix4 {
+        Matrix4([
+            [1., 0., 0., v.0],
+            [0., 1., 0., v.1],
+            [0., 0., 1., v.2],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Scale around origo, separately along each axis.
+    pub fn scale(v: Vector) -> Matrix4 {
+        Matrix4([
+            [v.0, 0., 0., 0.],
+            [0., v.1, 0., 0.],
+            [0., 0., v.2, 0.],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Rotate around `axis` through origo, in the same direction as `Vector::rotate`. The axis
+    /// does not have to be normalized.
+    pub fn rotation(axis: Vector, angle_deg: Unit) -> Matrix4 {
+        let Vector(x, y, z) = axis.normalize();
+        let angle = -UNIT_PI * angle_deg / 180.;
+        let (s, c) = (angle.sin(), angle.cos());
+        let t = 1. - c;
+
+        Matrix4([
+            [t * x * x + c, t * x * y - s * z, t * x * z + s * y, 0.],
+            [t * x * y + s * z, t * y * y + c, t * y * z - s * x, 0.],
+            [t * x * z - s * y, t * y * z + s * x, t * z * z + c, 0.],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Mirror through the plane through origo with normal `normal`. The normal does not have to
+    /// be normalized.
+    pub fn reflection(normal: Vector) -> Matrix4 {
+        let Vector(x, y, z) = normal.normalize();
+
+        Matrix4([
+            [1. - 2. * x * x, -2. * x * y, -2. * x * z, 0.],
+            [-2. * x * y, 1. - 2. * y * y, -2. * y * z, 0.],
+            [-2. * x * z, -2. * y * z, 1. - 2. * z * z, 0.],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// View transformation placing `eye` at origo, looking down the negative Z axis towards
+    /// `target` with `up` pointing along positive Y.
+    pub fn look_at(eye: Vector, target: Vector, up: Vector) -> Matrix4 {
+        let f = (target - eye).normalize();
+        let s = f.cross(up).normalize();
+        let u = s.cross(f);
+
+        Matrix4([
+            [s.0, s.1, s.2, -s.dot(eye)],
+            [u.0, u.1, u.2, -u.dot(eye)],
+            [-f.0, -f.1, -f.2, f.dot(eye)],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    pub fn transpose(&self) -> Matrix4 {
+        let m = &self.0;
+        let mut out = [[0.; 4]; 4];
+
+        for (r, row) in out.iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                *cell = m[c][r];
+            }
+        }
+
+        Matrix4(out)
+    }
+
+    /// Determinant of the linear part, negative when the transformation mirrors.
+    pub fn determinant3(&self) -> Unit {
+        let m = &self.0;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Inverse of an affine transformation, nothing if it collapses space.
+    pub fn inverse(&self) -> Option<Matrix4> {
+        let det = self.determinant3();
+        if det == 0. {
+            return None;
+        }
+
+        let m = &self.0;
+        let inv_det = 1. / det;
+
+        // Inverse of the linear part from its cofactors
+        let mut out = [[0.; 4]; 4];
+        out[0][0] = (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det;
+        out[0][1] = (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det;
+        out[0][2] = (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det;
+        out[1][0] = (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det;
+        out[1][1] = (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det;
+        out[1][2] = (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det;
+        out[2][0] = (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det;
+        out[2][1] = (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det;
+        out[2][2] = (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det;
+        out[3][3] = 1.;
+
+        // Undo the translation
+        for row in out.iter_mut().take(3) {
+            row[3] = -(row[0] * m[0][3] + row[1] * m[1][3] + row[2] * m[2][3]);
+        }
+
+        Some(Matrix4(out))
+    }
+
+    /// Matrix for transforming normals, the inverse transpose of the linear part. Normals
+    /// transformed with it stay perpendicular to transformed surfaces but need renormalizing.
+    pub fn normal_matrix(&self) -> Matrix4 {
+        match self.inverse() {
+            Some(inverse) => inverse.transpose(),
+            None => *self,
+        }
+    }
+
+    pub fn transform_point(&self, v: Vector) -> Vector {
+        let m = &self.0;
+
+        Vector(
+            m[0][0] * v.0 + m[0][1] * v.1 + m[0][2] * v.2 + m[0][3],
+            m[1][0] * v.0 + m[1][1] * v.1 + m[1][2] * v.2 + m[1][3],
+            m[2][0] * v.0 + m[2][1] * v.1 + m[2][2] * v.2 + m[2][3],
+        )
+    }
+
+    /// Transform a direction, ignoring translation.
+    pub fn transform_vector(&self, v: Vector) -> Vector {
+        let m = &self.0;
+
+        Vector(
+            m[0][0] * v.0 + m[0][1] * v.1 + m[0][2] * v.2,
+            m[1][0] * v.0 + m[1][1] * v.1 + m[1][2] * v.2,
+            m[2][0] * v.0 + m[2][1] * v.1 + m[2][2] * v.2,
+        )
+    }
+}
+
+impl Mul for Matrix4 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (&self.0, &rhs.0);
+        let mut out = [[0.; 4]; 4];
+
+        for (r, row) in out.iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                *cell = (0..4).map(|k| a[r][k] * b[k][c]).sum();
+            }
+        }
+
+        Matrix4(out)
+    }
+}
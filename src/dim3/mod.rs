@@ -1,16 +1,37 @@
+mod aabb;
 mod bsp_node;
+mod capsule;
 mod csg;
 mod cube;
+mod cylinder;
+mod ellipsoid;
+mod exact;
+mod extrude;
+mod icosphere;
+mod matrix;
 mod plane;
+mod platonic;
 mod polygon;
+mod polyhedron;
+mod prism;
+mod project;
+mod quaternion;
+mod rounded_cube;
+mod slice;
 mod sphere;
+mod thread;
+mod torus;
 mod vector;
 mod vertex;
 
-pub use self::bsp_node::BspNode;
+pub use self::aabb::Aabb;
+pub use self::bsp_node::{BspNode, BspStats};
 pub use self::csg::Csg;
+pub use self::matrix::Matrix4;
 pub use self::plane::Plane;
 pub use self::polygon::Polygon;
+pub use self::quaternion::Quaternion;
+pub use self::thread::ThreadProfile;
 pub use self::vector::{IVector, Vector};
 pub use self::vertex::Vertex;
 
@@ -1,5 +1,5 @@
 use dim3::{Polygon, Vector, Vertex};
-use {Unit, EPSILON};
+use {CsgOptions, Unit, EPSILON};
 
 bitflags! {
     struct Location: u32 {
@@ -27,6 +27,20 @@ impl Plane {
         Plane(self.0.negate(), -self.1)
     }
 
+    /// Two unit axes spanning the plane, wound counter clockwise seen from the front. The first
+    /// axis follows the X axis as close as the plane allows, so the XY plane gets X and Y.
+    pub fn basis(&self) -> (Vector, Vector) {
+        let n = self.0;
+        let mut u = Vector(1., 0., 0.) - n * n.0;
+
+        if u.length() <= EPSILON {
+            u = Vector(0., 1., 0.) - n * n.1;
+        }
+
+        let u = u.normalize();
+        (u, n.cross(u))
+    }
+
     /// Split `polygon` by this plane if needed, then put the polygon or polygon fragments in the
     /// appropriate lists. Coplanar polygons go into either `coplanarFront` or `coplanarBack`
     /// depending on their orientation with respect to this plane. Polygons in front or in back of
@@ -39,6 +53,27 @@ impl Plane {
         front: &mut Collector,
         back: &mut Collector,
     ) {
+        self.split_polygon_with(
+            &CsgOptions::default(),
+            poly,
+            coplane_front,
+            coplane_back,
+            front,
+            back,
+        );
+    }
+
+    /// Like `split_polygon`, with the tolerance taken from `options`.
+    pub fn split_polygon_with(
+        &self,
+        options: &CsgOptions,
+        poly: &Polygon,
+        coplane_front: &mut Collector,
+        coplane_back: &mut Collector,
+        front: &mut Collector,
+        back: &mut Collector,
+    ) {
+        let epsilon = options.epsilon;
         let mut polygon_type = Location::NONE;
         let mut vertex_locs: Vec<Location> = Vec::with_capacity(poly.vertices.len());
         let vertices_num = poly.vertices.len();
@@ -47,9 +82,9 @@ impl Plane {
             let t = self.0.dot(v.position) - self.1;
 
             let loc = {
-                if t < -EPSILON {
+                if t < -epsilon {
                     Location::BACK
-                } else if t > EPSILON {
+                } else if t > epsilon {
                     Location::FRONT
                 } else {
                     Location::COPLANAR
@@ -0,0 +1,127 @@
+use dim3::icosphere::icosahedron_triangles;
+use dim3::Csg;
+use dim3::Vector;
+use Unit;
+
+/// Reverse `face` if it is wound clockwise seen from outside a solid centered at origo.
+fn wind_outwards(points: &[Vector], mut face: Vec<usize>) -> Vec<usize> {
+    let a = points[face[0]];
+    let b = points[face[1]];
+    let c = points[face[2]];
+
+    if (b - a).cross(c - a).dot(a + b + c) < 0. {
+        face.reverse();
+    }
+    face
+}
+
+impl Csg {
+    /// Regular tetrahedron centered at origo.
+    ///
+    /// * `radius` - Distance from origo to the corners
+    pub fn tetrahedron(radius: Unit) -> Csg {
+        let points: Vec<Vector> = [
+            Vector(1., 1., 1.),
+            Vector(1., -1., -1.),
+            Vector(-1., 1., -1.),
+            Vector(-1., -1., 1.),
+        ]
+        .iter()
+        .map(|p| p.normalize() * radius)
+        .collect();
+
+        let faces: Vec<Vec<usize>> = [[1, 2, 3], [0, 2, 3], [0, 1, 3], [0, 1, 2]]
+            .iter()
+            .map(|f| wind_outwards(&points, f.to_vec()))
+            .collect();
+
+        Csg::polyhedron(&points, &faces)
+    }
+
+    /// Regular octahedron centered at origo, with corners on the axes.
+    ///
+    /// * `radius` - Distance from origo to the corners
+    pub fn octahedron(radius: Unit) -> Csg {
+        let points = [
+            Vector(radius, 0., 0.),
+            Vector(-radius, 0., 0.),
+            Vector(0., radius, 0.),
+            Vector(0., -radius, 0.),
+            Vector(0., 0., radius),
+            Vector(0., 0., -radius),
+        ];
+
+        let mut faces: Vec<Vec<usize>> = Vec::with_capacity(8);
+        for &x in &[0, 1] {
+            for &y in &[2, 3] {
+                for &z in &[4, 5] {
+                    faces.push(wind_outwards(&points, vec![x, y, z]));
+                }
+            }
+        }
+
+        Csg::polyhedron(&points, &faces)
+    }
+
+    /// Regular dodecahedron centered at origo.
+    ///
+    /// * `radius` - Distance from origo to the corners
+    pub fn dodecahedron(radius: Unit) -> Csg {
+        // The dual of an icosahedron, every triangle becomes a corner and every corner a face.
+        let triangles = icosahedron_triangles();
+        let points: Vec<Vector> = triangles
+            .iter()
+            .map(|t| (t[0] + t[1] + t[2]).normalize() * radius)
+            .collect();
+
+        let mut corners: Vec<Vector> = Vec::new();
+        for t in &triangles {
+            for &c in t {
+                if corners.iter().all(|o| (*o - c).length() > 0.001) {
+                    corners.push(c);
+                }
+            }
+        }
+
+        let faces: Vec<Vec<usize>> = corners
+            .iter()
+            .map(|&corner| {
+                let axis_u = corner.make_orthogonal();
+                let axis_v = corner.cross(axis_u);
+
+                let mut face: Vec<(Unit, usize)> = triangles
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| t.iter().any(|&c| (c - corner).length() <= 0.001))
+                    .map(|(i, _)| {
+                        let p = points[i];
+                        (p.dot(axis_v).atan2(p.dot(axis_u)), i)
+                    })
+                    .collect();
+
+                // Counter clockwise around the corner direction
+                face.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                face.iter().map(|&(_, i)| i).collect()
+            })
+            .collect();
+
+        Csg::polyhedron(&points, &faces)
+    }
+
+    /// Regular icosahedron centered at origo, with flat faces unlike `Csg::icosphere`.
+    ///
+    /// * `radius` - Distance from origo to the corners
+    pub fn icosahedron(radius: Unit) -> Csg {
+        let triangles = icosahedron_triangles();
+        let mut points: Vec<Vector> = Vec::with_capacity(triangles.len() * 3);
+        let mut faces: Vec<Vec<usize>> = Vec::with_capacity(triangles.len());
+
+        for t in &triangles {
+            let first = points.len();
+            points.extend(t.iter().map(|&c| c * radius));
+            faces.push(vec![first, first + 1, first + 2]);
+        }
+
+        Csg::polyhedron(&points, &faces)
+    }
+}
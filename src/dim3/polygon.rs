@@ -1,4 +1,5 @@
-use dim3::{Plane, Vertex};
+use dim3::{Plane, Vector, Vertex};
+use EPSILON;
 
 /// Represents a convex polygon. The vertices used to initialize a polygon must be coplanar and
 /// form a convex loop. They do not have to be `Vertex` instances but they must behave similarly
@@ -26,6 +27,34 @@ impl Polygon {
         Polygon { vertices, plane }
     }
 
+    /// Polygon with flat vertex normals from a loop of positions. Repeated positions are dropped,
+    /// nothing is returned if the loop has no area left.
+    pub(crate) fn flat(positions: &[Vector]) -> Option<Polygon> {
+        let len = positions.len();
+        let mut points: Vec<Vector> = Vec::with_capacity(len);
+
+        for (i, &p) in positions.iter().enumerate() {
+            if (p - positions[(i + len - 1) % len]).length() > EPSILON {
+                points.push(p);
+            }
+        }
+
+        if points.len() < 3 {
+            return None;
+        }
+
+        let normal = (points[1] - points[0]).cross(points[2] - points[0]);
+        if normal.length() <= EPSILON * EPSILON {
+            return None;
+        }
+
+        let normal = normal.normalize();
+        Some(Polygon {
+            vertices: points.iter().map(|&p| Vertex::new(p, normal)).collect(),
+            plane: Plane(normal, normal.dot(points[0])),
+        })
+    }
+
     pub fn flip(&mut self) {
         self.vertices.reverse();
 
@@ -0,0 +1,98 @@
+use dim2::{triangulate, Point};
+use dim3::Csg;
+use dim3::{Plane, Polygon, Vector, Vertex};
+use EPSILON;
+
+/// Normal of a polygon loop using Newell's method, which also works for concave and slightly
+/// non-planar loops.
+fn newell_normal(loop_points: &[Vector]) -> Vector {
+    let len = loop_points.len();
+    (0..len).fold(Vector(0., 0., 0.), |acc, i| {
+        let a = loop_points[i];
+        let b = loop_points[(i + 1) % len];
+        acc + Vector(
+            (a.1 - b.1) * (a.2 + b.2),
+            (a.2 - b.2) * (a.0 + b.0),
+            (a.0 - b.0) * (a.1 + b.1),
+        )
+    })
+}
+
+/// Check if the loop lies in `plane` and turns the same way at every corner.
+fn is_planar_convex(loop_points: &[Vector], plane: &Plane) -> bool {
+    let len = loop_points.len();
+
+    (0..len).all(|i| {
+        let prev = loop_points[(i + len - 1) % len];
+        let cur = loop_points[i];
+        let next = loop_points[(i + 1) % len];
+
+        (plane.0.dot(cur) - plane.1).abs() <= EPSILON
+            && (cur - prev).cross(next - cur).dot(plane.0) > EPSILON
+    })
+}
+
+impl Csg {
+    /// Build a solid from an indexed face list. Faces are wound counter clockwise seen from the
+    /// outside. Planar convex faces become one polygon each, other faces are triangulated. All
+    /// vertex normals are flat.
+    ///
+    /// * `points` - Corner positions shared between faces
+    /// * `faces` - Indices into `points`, one loop per face
+    ///
+    /// Panics if a face refers to a point out of range.
+    pub fn polyhedron(points: &[Vector], faces: &[Vec<usize>]) -> Csg {
+        let mut polys: Vec<Polygon> = Vec::new();
+
+        for face in faces {
+            let loop_points: Vec<Vector> = face.iter().map(|&i| points[i]).collect();
+
+            if loop_points.len() < 3 {
+                continue;
+            }
+
+            let normal = newell_normal(&loop_points);
+            if normal.length() <= EPSILON {
+                continue;
+            }
+
+            let normal = normal.normalize();
+            let plane = Plane(normal, normal.dot(loop_points[0]));
+
+            if is_planar_convex(&loop_points, &plane) {
+                polys.push(Polygon {
+                    vertices: loop_points
+                        .iter()
+                        .map(|&p| Vertex::new(p, normal))
+                        .collect(),
+                    plane,
+                });
+                continue;
+            }
+
+            // Flatten onto the plane of the face, keeping counter clockwise winding.
+            let axis_u = normal.make_orthogonal();
+            let axis_v = normal.cross(axis_u);
+            let flat: Vec<Point> = loop_points
+                .iter()
+                .map(|&p| Point(p.dot(axis_u), p.dot(axis_v)))
+                .collect();
+
+            for tri in triangulate(&flat) {
+                let corners = [
+                    loop_points[tri[0]],
+                    loop_points[tri[1]],
+                    loop_points[tri[2]],
+                ];
+                let plane = Plane::from_points(corners[0], corners[1], corners[2]);
+
+                polys.push(Polygon {
+                    vertices: corners.iter().map(|&p| Vertex::new(p, plane.0)).collect(),
+                    plane,
+                });
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+}
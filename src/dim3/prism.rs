@@ -0,0 +1,52 @@
+use dim3::Csg;
+use dim3::Vector;
+use {Unit, UNIT_PI};
+
+/// Corners of a regular polygon in the XY plane, counter clockwise.
+fn regular_polygon(sides: usize, radius: Unit, z: Unit) -> Vec<Vector> {
+    (0..sides)
+        .map(|i| {
+            let angle = (i as Unit) / (sides as Unit) * UNIT_PI * 2.0;
+            Vector(radius * angle.cos(), radius * angle.sin(), z)
+        })
+        .collect()
+}
+
+impl Csg {
+    /// Prism with a regular polygon as base, standing on the XY plane.
+    ///
+    /// * `sides` - Number of sides of the base
+    /// * `radius` - Distance from the Z axis to the corners of the base
+    /// * `height` - Extent along the Z axis
+    pub fn prism(sides: usize, radius: Unit, height: Unit) -> Csg {
+        let mut points = regular_polygon(sides, radius, 0.);
+        points.append(&mut regular_polygon(sides, radius, height));
+
+        let mut faces: Vec<Vec<usize>> =
+            vec![(0..sides).rev().collect(), (sides..2 * sides).collect()];
+        for i in 0..sides {
+            let j = (i + 1) % sides;
+            faces.push(vec![i, j, j + sides, i + sides]);
+        }
+
+        Csg::polyhedron(&points, &faces)
+    }
+
+    /// Pyramid with a regular polygon as base, standing on the XY plane with the apex on the Z
+    /// axis.
+    ///
+    /// * `sides` - Number of sides of the base
+    /// * `radius` - Distance from the Z axis to the corners of the base
+    /// * `height` - Height of the apex
+    pub fn pyramid(sides: usize, radius: Unit, height: Unit) -> Csg {
+        let mut points = regular_polygon(sides, radius, 0.);
+        points.push(Vector(0., 0., height));
+
+        let mut faces: Vec<Vec<usize>> = vec![(0..sides).rev().collect()];
+        for i in 0..sides {
+            faces.push(vec![i, (i + 1) % sides, sides]);
+        }
+
+        Csg::polyhedron(&points, &faces)
+    }
+}
@@ -0,0 +1,47 @@
+use dim2;
+use dim2::{LineStrip, Point};
+use dim3::{Csg, Plane};
+use EPSILON;
+
+impl Csg {
+    /// Silhouette of the solid seen along the normal of `plane`, like OpenSCAD's `projection()`.
+    /// Coordinates are measured along `Plane::basis`, so projecting onto the XY plane keeps X and
+    /// Y. Outlines are clockwise like `dim2::rectangle`.
+    pub fn project(&self, plane: &Plane) -> dim2::Csg {
+        let (axis_u, axis_v) = plane.basis();
+
+        // The faces seen from the front of the plane cover the whole silhouette of a closed solid.
+        let mut pieces: Vec<dim2::Csg> = self
+            .polygons
+            .iter()
+            .filter(|poly| poly.plane.0.dot(plane.0) > EPSILON)
+            .map(|poly| {
+                let points: Vec<Point> = poly
+                    .vertices
+                    .iter()
+                    .rev()
+                    .map(|v| Point(v.position.dot(axis_u), v.position.dot(axis_v)))
+                    .collect();
+
+                LineStrip::from_points(points).enclose().build()
+            })
+            .collect();
+
+        // Merge pairwise so each union works on pieces of similar size
+        while pieces.len() > 1 {
+            let mut merged: Vec<dim2::Csg> = Vec::with_capacity(pieces.len() / 2 + 1);
+
+            for pair in pieces.chunks(2) {
+                if pair.len() == 2 {
+                    merged.push(dim2::Csg::union(&pair[0], &pair[1]));
+                } else {
+                    merged.push(pair[0].clone());
+                }
+            }
+
+            pieces = merged;
+        }
+
+        pieces.pop().unwrap_or_else(dim2::Csg::new)
+    }
+}
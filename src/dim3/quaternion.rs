@@ -0,0 +1,115 @@
+use dim3::{Matrix4, Vector};
+use std::ops::Mul;
+use {Unit, UNIT_PI};
+
+/// Rotation stored as a unit quaternion, the vector part and the scalar part. Rotations are
+/// composed like `Matrix4`, in `a * b` the rotation `b` is applied first.
+#[derive(Clone, Copy, Debug)]
+pub struct Quaternion(pub Vector, pub Unit);
+
+impl Quaternion {
+    pub fn identity() -> Quaternion {
+        Quaternion(Vector(0., 0., 0.), 1.)
+    }
+
+    /// Rotate around `axis` through origo, in the same direction as `Vector::rotate`. The axis
+    /// does not have to be normalized.
+    pub fn from_axis_angle(axis: Vector, angle_deg: Unit) -> Quaternion {
+        let half = -UNIT_PI * angle_deg / 360.;
+        Quaternion(axis.normalize() * half.sin(), half.cos())
+    }
+
+    /// Rotate around the X axis first, then Y and last Z, each in the same direction as
+    /// `Vector::rotate`.
+    pub fn from_euler(x_deg: Unit, y_deg: Unit, z_deg: Unit) -> Quaternion {
+        Quaternion::from_axis_angle(Vector(0., 0., 1.), z_deg)
+            * Quaternion::from_axis_angle(Vector(0., 1., 0.), y_deg)
+            * Quaternion::from_axis_angle(Vector(1., 0., 0.), x_deg)
+    }
+
+    pub fn dot(&self, other: Quaternion) -> Unit {
+        self.0.dot(other.0) + self.1 * other.1
+    }
+
+    pub fn length(&self) -> Unit {
+        self.dot(*self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Quaternion {
+        let len = self.length();
+        Quaternion(self.0 / len, self.1 / len)
+    }
+
+    /// The opposite rotation.
+    pub fn conjugate(&self) -> Quaternion {
+        Quaternion(-self.0, self.1)
+    }
+
+    /// Spherical interpolation along the shortest arc from `self` at `t = 0` to `other` at
+    /// `t = 1`.
+    pub fn slerp(&self, other: Quaternion, t: Unit) -> Quaternion {
+        let mut other = other;
+        let mut cos_theta = self.dot(other);
+
+        // q and -q are the same rotation, take the short way around
+        if cos_theta < 0. {
+            other = Quaternion(-other.0, -other.1);
+            cos_theta = -cos_theta;
+        }
+
+        let (a, b) = if cos_theta > 1. - ::EPSILON {
+            // Nearly parallel, fall back to linear interpolation
+            (1. - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (
+                ((1. - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Quaternion(self.0 * a + other.0 * b, self.1 * a + other.1 * b).normalize()
+    }
+
+    pub fn rotate_vector(&self, v: Vector) -> Vector {
+        (*self * Quaternion(v, 0.) * self.conjugate()).0
+    }
+
+    pub fn to_matrix(&self) -> Matrix4 {
+        let Quaternion(Vector(x, y, z), w) = self.normalize();
+
+        Matrix4([
+            [
+                1. - 2. * (y * y + z * z),
+                2. * (x * y - w * z),
+                2. * (x * z + w * y),
+                0.,
+            ],
+            [
+                2. * (x * y + w * z),
+                1. - 2. * (x * x + z * z),
+                2. * (y * z - w * x),
+                0.,
+            ],
+            [
+                2. * (x * z - w * y),
+                2. * (y * z + w * x),
+                1. - 2. * (x * x + y * y),
+                0.,
+            ],
+            [0., 0., 0., 1.],
+        ])
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let Quaternion(a, aw) = self;
+        let Quaternion(b, bw) = rhs;
+
+        Quaternion(b * aw + a * bw + a.cross(b), aw * bw - a.dot(b))
+    }
+}
@@ -0,0 +1,129 @@
+use dim3::Csg;
+use dim3::{Polygon, Vector, Vertex};
+use {Unit, EPSILON, UNIT_PI};
+
+/// Cosine and sine of `segments + 1` angles over a quarter turn, with exact end points.
+fn quarter_turn(segments: usize) -> Vec<(Unit, Unit)> {
+    (0..=segments)
+        .map(|k| {
+            if k == 0 {
+                (1., 0.)
+            } else if k == segments {
+                (0., 1.)
+            } else {
+                let angle = (k as Unit) / (segments as Unit) * UNIT_PI * 0.5;
+                (angle.cos(), angle.sin())
+            }
+        })
+        .collect()
+}
+
+impl Csg {
+    /// Box with filleted edges and spherical corners.
+    ///
+    /// * `dim` - Dimensions of box
+    /// * `radius` - Radius of edges and corners, clamped to half of the smallest dimension
+    /// * `segments` - Number of subdivisions of each quarter turn of an edge
+    /// * `center` - Center box around origo, otherwise it starts at origo
+    pub fn rounded_cube(dim: Vector, radius: Unit, segments: usize, center: bool) -> Csg {
+        if radius <= EPSILON || segments == 0 {
+            return Csg::cube(dim, center);
+        }
+
+        let radius = radius.min(dim.0.min(dim.1).min(dim.2) * 0.5);
+
+        let inner = dim * 0.5 - Vector(radius, radius, radius);
+        let quarter = quarter_turn(segments);
+
+        // Columns go around the Z axis one quadrant at a time, the corner point of each quadrant
+        // is repeated so the flat faces end up between quadrants.
+        let mut columns: Vec<(Unit, Unit, Unit, Unit)> = Vec::with_capacity(4 * quarter.len());
+        for quadrant in 0..4 {
+            for &(c, s) in &quarter {
+                let (dx, dy) = match quadrant {
+                    0 => (c, s),
+                    1 => (-s, c),
+                    2 => (-c, -s),
+                    _ => (s, -c),
+                };
+                let (ox, oy) = match quadrant {
+                    0 => (inner.0, inner.1),
+                    1 => (-inner.0, inner.1),
+                    2 => (-inner.0, -inner.1),
+                    _ => (inner.0, -inner.1),
+                };
+                columns.push((dx, dy, ox, oy));
+            }
+        }
+
+        // Rows go from the top to the bottom, repeating the equator for the same reason.
+        let mut rows: Vec<(Unit, Unit, Unit)> = Vec::with_capacity(2 * quarter.len());
+        for &(c, s) in &quarter {
+            rows.push((c, s, inner.2));
+        }
+        for &(c, s) in quarter.iter().rev() {
+            rows.push((-c, s, -inner.2));
+        }
+
+        let make_vertex = |row: usize, column: usize| {
+            let (dz, phi_sin, oz) = rows[row];
+            let (dx, dy, ox, oy) = columns[column];
+            let d = Vector(dx * phi_sin, dy * phi_sin, dz);
+
+            Vertex::new(Vector(ox, oy, oz) + d * radius, d)
+        };
+
+        let mut polys: Vec<Polygon> = Vec::new();
+
+        for row in 0..rows.len() - 1 {
+            for column in 0..columns.len() {
+                let next = (column + 1) % columns.len();
+                let corners = [
+                    make_vertex(row, column),
+                    make_vertex(row + 1, column),
+                    make_vertex(row + 1, next),
+                    make_vertex(row, next),
+                ];
+
+                // Drop corners collapsed at the poles
+                let mut verts: Vec<Vertex> = Vec::with_capacity(4);
+                for (i, v) in corners.iter().enumerate() {
+                    let prev = corners[(i + 3) % 4];
+                    if (v.position - prev.position).length() > EPSILON {
+                        verts.push(*v);
+                    }
+                }
+
+                if verts.len() >= 3 {
+                    polys.push(Polygon::new(verts));
+                }
+            }
+        }
+
+        // Top and bottom faces between the pole points of each quadrant
+        let quadrant_starts: Vec<usize> = (0..4).map(|q| q * quarter.len()).collect();
+        let last = rows.len() - 1;
+
+        // They have no area left when the radius is half the width or depth
+        let top: Vec<Vector> = quadrant_starts
+            .iter()
+            .map(|&column| make_vertex(0, column).position)
+            .collect();
+        let bottom: Vec<Vector> = quadrant_starts
+            .iter()
+            .rev()
+            .map(|&column| make_vertex(last, column).position)
+            .collect();
+
+        polys.extend(Polygon::flat(&top));
+        polys.extend(Polygon::flat(&bottom));
+
+        let csg = Csg::from_polygons(polys);
+
+        if center {
+            csg
+        } else {
+            csg.translate(dim * 0.5)
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use dim2;
+use dim2::{Line, LineStrip, Point};
+use dim3::{Csg, Plane, Vector};
+use std::cmp::Ordering;
+use {CsgOptions, Unit};
+
+impl Csg {
+    /// Cross section of the solid where it is cut by `plane`. Coordinates are measured along
+    /// `Plane::basis`. Outlines are clockwise like `dim2::rectangle` and holes counter clockwise.
+    /// Faces lying in the plane count as being in front of it, so cutting exactly through the
+    /// bottom of a cube gives nothing while cutting through the top gives the full square.
+    pub fn slice(&self, plane: &Plane) -> dim2::Csg {
+        self.slice_with(plane, &CsgOptions::default())
+    }
+
+    /// `slice` with the tolerance in `options`.
+    pub fn slice_with(&self, plane: &Plane, options: &CsgOptions) -> dim2::Csg {
+        let (axis_u, axis_v) = plane.basis();
+        let flatten = |v: Vector| Point(v.dot(axis_u), v.dot(axis_v));
+        let epsilon = options.epsilon;
+        let mut segments: Vec<Line> = Vec::new();
+
+        for poly in &self.polygons {
+            let len = poly.vertices.len();
+            let dists: Vec<Unit> = poly
+                .vertices
+                .iter()
+                .map(|v| plane.0.dot(v.position) - plane.1)
+                .collect();
+
+            // Where the outline of the polygon passes the plane, and whether into its front
+            let mut crossings: Vec<(Vector, bool)> = Vec::with_capacity(2);
+            for i in 0..len {
+                let j = (i + 1) % len;
+                let behind_i = dists[i] < -epsilon;
+                let behind_j = dists[j] < -epsilon;
+
+                if behind_i != behind_j {
+                    let t = dists[i] / (dists[i] - dists[j]);
+                    let vi = poly.vertices[i].position;
+                    let vj = poly.vertices[j].position;
+                    crossings.push((vi.lerp(vj, t), behind_i));
+                }
+            }
+
+            // Along the cut the polygon starts outside, so it covers every other stretch between
+            // crossings however concave it is
+            let along = poly.plane.0.cross(plane.0);
+            crossings.sort_by(|a, b| {
+                along
+                    .dot(a.0)
+                    .partial_cmp(&along.dot(b.0))
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            // Going from where the outline enters the front to where it leaves keeps the solid to
+            // the right of the line, whichever way the polygon faces
+            for pair in crossings.chunks(2) {
+                if pair.len() < 2 || (pair[1].0 - pair[0].0).length() <= epsilon {
+                    continue;
+                }
+
+                let (from, to) = if pair[0].1 {
+                    (pair[0].0, pair[1].0)
+                } else {
+                    (pair[1].0, pair[0].0)
+                };
+                segments.push(Line::new(flatten(from), flatten(to)));
+            }
+        }
+
+        let mut lines: Vec<Line> = Vec::with_capacity(segments.len());
+        for points in dim2::Csg::from_lines(segments).to_loops() {
+            lines.append(&mut LineStrip::from_points(points).enclose().build_lines());
+        }
+
+        dim2::Csg::from_lines(lines)
+    }
+}
@@ -9,7 +9,7 @@ impl Csg {
             let d = Vector(theta.cos() * phi_sin, theta.sin() * phi_sin, phi.cos());
 
             Vertex::new(d * radius, d)
-        };
+        }
 
         let mut polys: Vec<Polygon> = Vec::new();
         let delta_theta = UNIT_PI * 2.0 / (slices as Unit);
@@ -41,8 +41,8 @@ impl Csg {
 
             polys.push(Polygon::new(vec![
                 make_vertex(radius, i0 * delta_theta, j0 * delta_phi),
-                make_vertex(radius, i1 * delta_theta, j0 * delta_phi),
                 make_vertex(radius, i0 * delta_theta, j1 * delta_phi),
+                make_vertex(radius, i1 * delta_theta, j0 * delta_phi),
             ]));
         }
 
@@ -65,26 +65,26 @@ impl Csg {
 
                 polys.push(Polygon::new(vec![
                     make_vertex(radius, i1 * delta_theta, j1 * delta_phi),
-                    make_vertex(radius, i2 * delta_theta, j2 * delta_phi),
                     make_vertex(radius, i0 * delta_theta, j2 * delta_phi),
+                    make_vertex(radius, i2 * delta_theta, j2 * delta_phi),
                 ]));
 
                 polys.push(Polygon::new(vec![
                     make_vertex(radius, i1 * delta_theta, j1 * delta_phi),
-                    make_vertex(radius, i0 * delta_theta, j0 * delta_phi),
                     make_vertex(radius, i2 * delta_theta, j0 * delta_phi),
+                    make_vertex(radius, i0 * delta_theta, j0 * delta_phi),
                 ]));
 
                 polys.push(Polygon::new(vec![
                     make_vertex(radius, i1 * delta_theta, j1 * delta_phi),
-                    make_vertex(radius, i0 * delta_theta, j2 * delta_phi),
                     make_vertex(radius, i0 * delta_theta, j0 * delta_phi),
+                    make_vertex(radius, i0 * delta_theta, j2 * delta_phi),
                 ]));
 
                 polys.push(Polygon::new(vec![
                     make_vertex(radius, i1 * delta_theta, j1 * delta_phi),
-                    make_vertex(radius, i2 * delta_theta, j0 * delta_phi),
                     make_vertex(radius, i2 * delta_theta, j2 * delta_phi),
+                    make_vertex(radius, i2 * delta_theta, j0 * delta_phi),
                 ]));
             }
         }
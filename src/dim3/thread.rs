@@ -0,0 +1,144 @@
+use dim3::Csg;
+use dim3::{Polygon, Vector};
+use {Unit, UNIT_PI};
+
+/// Shape of a thread tooth, seen in a cut through the axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThreadProfile {
+    /// ISO metric, 60 degree flanks with a flat crest of 1/8 pitch and a flat root of 1/4 pitch.
+    Iso,
+    /// Square teeth, crest and root each half a pitch.
+    Square,
+}
+
+impl ThreadProfile {
+    /// Corners of the tooth over one pitch, as `(phase, height)` from 0 to 1.
+    fn corners(self) -> &'static [(Unit, Unit)] {
+        match self {
+            ThreadProfile::Iso => &[(0., 0.), (0.25, 0.), (0.5625, 1.), (0.6875, 1.), (1., 0.)],
+            ThreadProfile::Square => &[(0., 0.), (0.5, 0.), (0.5, 1.), (1., 1.), (1., 0.)],
+        }
+    }
+
+    /// Tooth height at `phase` within the pitch, from 0 at the root to 1 at the crest.
+    fn height(self, phase: Unit) -> Unit {
+        let corners = self.corners();
+        let phase = phase - phase.floor();
+
+        for pair in corners.windows(2) {
+            let (u0, h0) = pair[0];
+            let (u1, h1) = pair[1];
+
+            if phase < u1 {
+                return if u1 > u0 {
+                    h0 + (h1 - h0) * (phase - u0) / (u1 - u0)
+                } else {
+                    h1
+                };
+            }
+        }
+
+        corners[corners.len() - 1].1
+    }
+}
+
+impl Csg {
+    /// Right hand external thread along the Z axis, starting at origo.
+    ///
+    /// The surface follows the helix exactly at the profile corners when `segments` is a multiple
+    /// of 16.
+    ///
+    /// * `pitch` - Distance between teeth along the axis
+    /// * `major_d` - Diameter at the crest of the teeth
+    /// * `minor_d` - Diameter at the root of the teeth
+    /// * `length` - Extent along the Z axis
+    /// * `profile` - Shape of the teeth
+    /// * `segments` - Number of subdivisions per turn
+    pub fn thread(
+        pitch: Unit,
+        major_d: Unit,
+        minor_d: Unit,
+        length: Unit,
+        profile: ThreadProfile,
+        segments: usize,
+    ) -> Csg {
+        let segments = segments.max(3);
+
+        // Rows are a fraction of a pitch apart so every grid diagonal follows the helix.
+        let row_step = pitch / (segments as Unit);
+        let rows = (length / row_step).ceil().max(1.) as usize;
+
+        let ring = |j: usize| -> Vec<Vector> {
+            let z = ((j as Unit) * row_step).min(length);
+
+            (0..segments)
+                .map(|i| {
+                    let turn = (i as Unit) / (segments as Unit);
+                    let angle = turn * UNIT_PI * 2.;
+                    let h = profile.height(z / pitch - turn);
+                    let radius = (minor_d + (major_d - minor_d) * h) * 0.5;
+
+                    Vector(radius * angle.cos(), radius * angle.sin(), z)
+                })
+                .collect()
+        };
+
+        let mut polys: Vec<Polygon> = Vec::new();
+        let mut bottom = ring(0);
+        let first = bottom.clone();
+
+        for j in 0..rows {
+            let top = ring(j + 1);
+
+            for i in 0..segments {
+                let k = (i + 1) % segments;
+                polys.extend(Polygon::flat(&[bottom[i], bottom[k], top[k]]));
+                polys.extend(Polygon::flat(&[bottom[i], top[k], top[i]]));
+            }
+
+            bottom = top;
+        }
+
+        // Caps, fanned from the axis
+        let start = Vector(0., 0., 0.);
+        let end = Vector(0., 0., length);
+        for i in 0..segments {
+            let k = (i + 1) % segments;
+            polys.extend(Polygon::flat(&[start, first[k], first[i]]));
+            polys.extend(Polygon::flat(&[end, bottom[i], bottom[k]]));
+        }
+
+        Csg::from_polygons(polys)
+    }
+
+    /// Cutting tool for an internal thread, to be subtracted from a nut or a threaded hole. The
+    /// tool is the mating external thread grown by `clearance` and extended one pitch past both
+    /// ends, so it cuts cleanly through a part spanning `0` to `length` along the Z axis.
+    ///
+    /// * `pitch` - Distance between teeth along the axis
+    /// * `major_d` - Diameter at the root of the internal teeth
+    /// * `minor_d` - Diameter at the crest of the internal teeth
+    /// * `length` - Extent along the Z axis of the part to thread
+    /// * `profile` - Shape of the teeth
+    /// * `segments` - Number of subdivisions per turn
+    /// * `clearance` - Extra diameter for fit between mating threads
+    pub fn internal_thread(
+        pitch: Unit,
+        major_d: Unit,
+        minor_d: Unit,
+        length: Unit,
+        profile: ThreadProfile,
+        segments: usize,
+        clearance: Unit,
+    ) -> Csg {
+        Csg::thread(
+            pitch,
+            major_d + clearance,
+            minor_d + clearance,
+            length + 2. * pitch,
+            profile,
+            segments,
+        )
+        .translate(Vector(0., 0., -pitch))
+    }
+}
@@ -0,0 +1,43 @@
+use dim3::Csg;
+use dim3::{Polygon, Vector, Vertex};
+use {Unit, UNIT_PI};
+
+impl Csg {
+    /// Torus centered at origo, lying in the XY plane.
+    ///
+    /// * `major` - Distance from origo to the center of the tube
+    /// * `minor` - Radius of the tube
+    /// * `rings` - Number of subdivisions around the Z axis
+    /// * `sides` - Number of subdivisions around the tube
+    pub fn torus(major: Unit, minor: Unit, rings: usize, sides: usize) -> Csg {
+        fn make_vertex(major: Unit, minor: Unit, u: Unit, v: Unit) -> Vertex {
+            let d = Vector(v.cos() * u.cos(), v.cos() * u.sin(), v.sin());
+            let center = Vector(u.cos(), u.sin(), 0.) * major;
+
+            Vertex::new(center + d * minor, d)
+        }
+
+        let mut polys: Vec<Polygon> = Vec::new();
+        let delta_u = UNIT_PI * 2.0 / (rings as Unit);
+        let delta_v = UNIT_PI * 2.0 / (sides as Unit);
+
+        for i in 0..rings {
+            let u0 = (i as Unit) * delta_u;
+            let u1 = ((i + 1) % rings) as Unit * delta_u;
+
+            for j in 0..sides {
+                let v0 = (j as Unit) * delta_v;
+                let v1 = ((j + 1) % sides) as Unit * delta_v;
+
+                polys.push(Polygon::new(vec![
+                    make_vertex(major, minor, u0, v0),
+                    make_vertex(major, minor, u1, v0),
+                    make_vertex(major, minor, u1, v1),
+                    make_vertex(major, minor, u0, v1),
+                ]));
+            }
+        }
+
+        Csg::from_polygons(polys)
+    }
+}
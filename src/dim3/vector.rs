@@ -8,7 +8,7 @@ use {IUnit, Unit, UNIT_PI};
 ///
 /// ```
 /// use rscsg::dim3::Vector;
-/// Vector(1f32, 2f32, 3f32);
+/// Vector(1., 2., 3.);
 /// ```
 
 #[derive(Clone, Copy, Debug)]
@@ -79,7 +79,13 @@ impl Vector {
 
     /// Make a new vector which is orthogonal to `self`.
     pub fn make_orthogonal(&self) -> Self {
-        *self
+        let helper = if self.0.abs() < 0.5 {
+            Vector(1., 0., 0.)
+        } else {
+            Vector(0., 1., 0.)
+        };
+
+        self.cross(helper).normalize()
     }
 }
 
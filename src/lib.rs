@@ -3,14 +3,32 @@ pub mod dim3;
 
 #[macro_use]
 extern crate bitflags;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+mod options;
+mod predicates;
 
 #[cfg(test)]
 mod tests;
 
-use std::f32::consts::PI;
+pub use options::{CsgOptions, SplitHeuristic};
 
+/// Floating point type used for all coordinates, `f64` with the `f64` feature.
+#[cfg(not(feature = "f64"))]
 pub type Unit = f32;
-pub const UNIT_PI: Unit = PI;
+#[cfg(feature = "f64")]
+pub type Unit = f64;
+
+#[cfg(not(feature = "f64"))]
+pub const UNIT_PI: Unit = std::f32::consts::PI;
+#[cfg(feature = "f64")]
+pub const UNIT_PI: Unit = std::f64::consts::PI;
+
+/// Distance within which points are considered to lie on a plane.
+#[cfg(not(feature = "f64"))]
 pub const EPSILON: Unit = 0.00001;
+#[cfg(feature = "f64")]
+pub const EPSILON: Unit = 0.000000001;
 
 pub type IUnit = i32;
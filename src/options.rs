@@ -0,0 +1,60 @@
+use {Unit, EPSILON};
+
+/// Settings for boolean operations and BSP trees, shared by `dim2` and `dim3`.
+///
+/// ```
+/// use rscsg::CsgOptions;
+/// // Jewelry modelled in meters
+/// let options = CsgOptions {
+///     epsilon: 0.0000001,
+///     ..CsgOptions::default()
+/// };
+///
+/// // Architecture modelled in millimeters
+/// let options = CsgOptions {
+///     robust: true,
+///     ..CsgOptions::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CsgOptions {
+    /// Distance within which points are considered to lie on a splitting plane. Should be well
+    /// below the smallest feature of the models and well above the rounding errors at their
+    /// largest coordinates.
+    pub epsilon: Unit,
+    /// Decide the side of splitting planes with exact orientation tests instead of `epsilon`.
+    /// Points cut from edges are kept as the planes they were cut with, so they are classified
+    /// exactly however often they are split again, and are rounded the same way wherever they
+    /// turn up. Vertices ending on an edge of a neighboring polygon are added to it. Slower, but
+    /// booleans of closed solids with touching or nearly coplanar faces come out closed.
+    pub robust: bool,
+    /// How `dim3::BspNode` picks the plane to split on.
+    pub split: SplitHeuristic,
+}
+
+/// Strategy for picking the splitting plane of a BSP node among the polygons reaching it. The
+/// result of boolean operations is the same, the number of fragments and the depth of the tree
+/// differ.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SplitHeuristic {
+    /// Plane of the first polygon, the fastest to build.
+    First,
+    /// Best of this many pseudo randomly sampled polygons, by the fewest splits and then the most
+    /// even balance.
+    RandomSample(usize),
+    /// Plane splitting the fewest polygons, among up to 32 candidates spread evenly over them.
+    MinimizeSplits,
+    /// Plane with the most even number of polygons in front and behind, among up to 32
+    /// candidates spread evenly over them.
+    Balance,
+}
+
+impl Default for CsgOptions {
+    fn default() -> CsgOptions {
+        CsgOptions {
+            epsilon: EPSILON,
+            robust: false,
+            split: SplitHeuristic::First,
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Adaptive precision orientation tests, after Shewchuk's "Adaptive Precision Floating-Point
+//! Arithmetic and Fast Robust Geometric Predicates". The plain floating point result is used when
+//! it is certainly right, otherwise the determinant is evaluated exactly with expansions, lists of
+//! non-overlapping `f64` components in increasing magnitude.
+
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Sub};
+use Unit;
+
+/// Machine epsilon of `f64` as used in the error bounds, 2^-53.
+const F64_EPSILON: f64 = 1.1102230246251565e-16;
+const ORIENT2D_BOUND: f64 = (3. + 16. * F64_EPSILON) * F64_EPSILON;
+const ORIENT3D_BOUND: f64 = (7. + 56. * F64_EPSILON) * F64_EPSILON;
+
+type Expansion = Vec<f64>;
+
+/// Coordinate widened to `f64` for the predicates.
+#[cfg(not(feature = "f64"))]
+pub fn wide(x: Unit) -> f64 {
+    f64::from(x)
+}
+
+/// Coordinate widened to `f64` for the predicates.
+#[cfg(feature = "f64")]
+pub fn wide(x: Unit) -> f64 {
+    x
+}
+
+/// Coordinate rounded back from `f64`.
+#[cfg(not(feature = "f64"))]
+pub fn narrow(x: f64) -> Unit {
+    x as Unit
+}
+
+/// Coordinate rounded back from `f64`.
+#[cfg(feature = "f64")]
+pub fn narrow(x: f64) -> Unit {
+    x
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`. Positive when counter clockwise, zero
+/// exactly when the points are collinear.
+pub fn orient2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let left = (a[0] - c[0]) * (b[1] - c[1]);
+    let right = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = left - right;
+
+    if det.abs() > ORIENT2D_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+
+    let acx = diff(a[0], c[0]);
+    let acy = diff(a[1], c[1]);
+    let bcx = diff(b[0], c[0]);
+    let bcy = diff(b[1], c[1]);
+
+    estimate(&sub(&mul(&acx, &bcy), &mul(&acy, &bcx)))
+}
+
+/// Six times the signed volume of the tetrahedron `a`, `b`, `c`, `d`. Positive when `d` lies on
+/// the side of `a`, `b`, `c` they are counter clockwise seen from, zero exactly when the points
+/// are coplanar.
+pub fn orient3d(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> f64 {
+    let (adx, ady, adz) = (a[0] - d[0], a[1] - d[1], a[2] - d[2]);
+    let (bdx, bdy, bdz) = (b[0] - d[0], b[1] - d[1], b[2] - d[2]);
+    let (cdx, cdy, cdz) = (c[0] - d[0], c[1] - d[1], c[2] - d[2]);
+
+    let det = adx * (bdy * cdz - bdz * cdy)
+        + bdx * (cdy * adz - cdz * ady)
+        + cdx * (ady * bdz - adz * bdy);
+    let permanent = adx.abs() * ((bdy * cdz).abs() + (bdz * cdy).abs())
+        + bdx.abs() * ((cdy * adz).abs() + (cdz * ady).abs())
+        + cdx.abs() * ((ady * bdz).abs() + (adz * bdy).abs());
+
+    if det.abs() > ORIENT3D_BOUND * permanent {
+        return -det;
+    }
+
+    let (adx, ady, adz) = (diff(a[0], d[0]), diff(a[1], d[1]), diff(a[2], d[2]));
+    let (bdx, bdy, bdz) = (diff(b[0], d[0]), diff(b[1], d[1]), diff(b[2], d[2]));
+    let (cdx, cdy, cdz) = (diff(c[0], d[0]), diff(c[1], d[1]), diff(c[2], d[2]));
+
+    let bc = sub(&mul(&bdy, &cdz), &mul(&bdz, &cdy));
+    let ca = sub(&mul(&cdy, &adz), &mul(&cdz, &ady));
+    let ab = sub(&mul(&ady, &bdz), &mul(&adz, &bdy));
+    let det = add(&add(&mul(&adx, &bc), &mul(&bdx, &ca)), &mul(&cdx, &ab));
+
+    -estimate(&det)
+}
+
+/// Numbers a `Polynomial` can be evaluated in.
+pub trait Arithmetic: Clone + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    fn from_f64(x: f64) -> Self;
+}
+
+impl Arithmetic for f64 {
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+}
+
+/// Polynomial in `f64` values whose sign is wanted, see `sign`.
+pub trait Polynomial {
+    fn eval<T: Arithmetic>(&self) -> T;
+}
+
+/// Exact sign of `p`. It is evaluated in floating point with a bound on the accumulated rounding
+/// error first, and again exactly only if the bound does not settle the sign.
+pub fn sign<P: Polynomial>(p: &P) -> Ordering {
+    let Bounded { value, error } = p.eval();
+
+    if value > error {
+        Ordering::Greater
+    } else if value < -error {
+        Ordering::Less
+    } else {
+        let Exact(e) = p.eval();
+        e.iter()
+            .rev()
+            .find(|&&component| component != 0.)
+            .map_or(Ordering::Equal, |&component| {
+                if component > 0. {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                }
+            })
+    }
+}
+
+/// Largest `Unit` not above `numerator / denominator`, exactly. The same point rounds to the same
+/// coordinates however its polynomials are put together.
+pub fn floor_quotient<P: Polynomial, Q: Polynomial>(numerator: &P, denominator: &Q) -> Unit {
+    // Offsets of the smallest steps away from zero would underflow
+    if sign(numerator) == Ordering::Equal {
+        return 0.;
+    }
+
+    let negative = sign(denominator) == Ordering::Less;
+    let compare = |r: Unit| {
+        let order = sign(&Offset(numerator, denominator, wide(r)));
+        if negative {
+            order.reverse()
+        } else {
+            order
+        }
+    };
+
+    // Start next to the quotient, from the exact values if rounding could have put the floating
+    // point ones more than a step of `Unit` off
+    let (n, d): (Bounded, Bounded) = (numerator.eval(), denominator.eval());
+    let estimate = if n.error + d.error * (n.value / d.value).abs()
+        < wide(Unit::EPSILON) * n.value.abs() / 4.
+    {
+        n.value / d.value
+    } else {
+        let (Exact(n), Exact(d)) = (numerator.eval(), denominator.eval());
+        estimate(&n) / estimate(&d)
+    };
+    let mut r = if estimate.is_finite() {
+        narrow(estimate)
+    } else {
+        0.
+    };
+
+    while compare(r) == Ordering::Less {
+        r = step(r, false);
+    }
+    while compare(step(r, true)) != Ordering::Less {
+        r = step(r, true);
+    }
+
+    r
+}
+
+/// The next `Unit` above `x`, or below it.
+fn step(x: Unit, up: bool) -> Unit {
+    if x == 0. {
+        let smallest = Unit::from_bits(1);
+        return if up { smallest } else { -smallest };
+    }
+
+    let bits = x.to_bits();
+    if (x > 0.) == up {
+        Unit::from_bits(bits + 1)
+    } else {
+        Unit::from_bits(bits - 1)
+    }
+}
+
+/// `numerator - r * denominator`, the sign comparing their quotient with `r`.
+struct Offset<'a, P: 'a, Q: 'a>(&'a P, &'a Q, f64);
+
+impl<'a, P: Polynomial, Q: Polynomial> Polynomial for Offset<'a, P, Q> {
+    fn eval<T: Arithmetic>(&self) -> T {
+        self.0.eval::<T>() - T::from_f64(self.2) * self.1.eval::<T>()
+    }
+}
+
+/// Floating point value with a bound on its distance from the exact result.
+#[derive(Clone, Copy)]
+struct Bounded {
+    value: f64,
+    error: f64,
+}
+
+impl Arithmetic for Bounded {
+    fn from_f64(x: f64) -> Bounded {
+        Bounded {
+            value: x,
+            error: 0.,
+        }
+    }
+}
+
+// Each bound adds the rounding of the result, and is grown to cover its own rounding
+
+impl Add for Bounded {
+    type Output = Bounded;
+
+    fn add(self, other: Bounded) -> Bounded {
+        let value = self.value + other.value;
+        let error = self.error + other.error + value.abs() * F64_EPSILON;
+        Bounded {
+            value,
+            error: error * (1. + 4. * F64_EPSILON),
+        }
+    }
+}
+
+impl Sub for Bounded {
+    type Output = Bounded;
+
+    fn sub(self, other: Bounded) -> Bounded {
+        let value = self.value - other.value;
+        let error = self.error + other.error + value.abs() * F64_EPSILON;
+        Bounded {
+            value,
+            error: error * (1. + 4. * F64_EPSILON),
+        }
+    }
+}
+
+impl Mul for Bounded {
+    type Output = Bounded;
+
+    fn mul(self, other: Bounded) -> Bounded {
+        let value = self.value * other.value;
+        let error = self.value.abs() * other.error
+            + other.value.abs() * self.error
+            + self.error * other.error
+            + value.abs() * F64_EPSILON;
+        Bounded {
+            value,
+            error: error * (1. + 8. * F64_EPSILON),
+        }
+    }
+}
+
+/// Exact value as an expansion.
+#[derive(Clone)]
+struct Exact(Expansion);
+
+impl Arithmetic for Exact {
+    fn from_f64(x: f64) -> Exact {
+        Exact(vec![x])
+    }
+}
+
+impl Add for Exact {
+    type Output = Exact;
+
+    fn add(self, other: Exact) -> Exact {
+        Exact(compress(&add(&self.0, &other.0)))
+    }
+}
+
+impl Sub for Exact {
+    type Output = Exact;
+
+    fn sub(self, other: Exact) -> Exact {
+        Exact(compress(&sub(&self.0, &other.0)))
+    }
+}
+
+impl Mul for Exact {
+    type Output = Exact;
+
+    fn mul(self, other: Exact) -> Exact {
+        Exact(compress(&mul(&self.0, &other.0)))
+    }
+}
+
+/// Sum and its rounding error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Product and its rounding error, exact thanks to the fused multiply add.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+fn diff(a: f64, b: f64) -> Expansion {
+    let (x, y) = two_sum(a, -b);
+    vec![y, x]
+}
+
+/// Add a single component, keeping the expansion non-overlapping.
+fn grow(e: &[f64], b: f64) -> Expansion {
+    let mut out = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+
+    for &component in e {
+        let (sum, error) = two_sum(q, component);
+        if error != 0. {
+            out.push(error);
+        }
+        q = sum;
+    }
+
+    out.push(q);
+    out
+}
+
+fn add(e: &[f64], f: &[f64]) -> Expansion {
+    f.iter()
+        .fold(e.to_vec(), |acc, &component| grow(&acc, component))
+}
+
+fn sub(e: &[f64], f: &[f64]) -> Expansion {
+    f.iter()
+        .fold(e.to_vec(), |acc, &component| grow(&acc, -component))
+}
+
+fn scale(e: &[f64], b: f64) -> Expansion {
+    e.iter().fold(Vec::new(), |acc, &component| {
+        let (product, error) = two_product(component, b);
+        grow(&grow(&acc, error), product)
+    })
+}
+
+fn mul(e: &[f64], f: &[f64]) -> Expansion {
+    f.iter().fold(Vec::new(), |acc, &component| {
+        add(&acc, &scale(e, component))
+    })
+}
+
+/// The same value in as few components as possible, so products of long polynomials stay short.
+fn compress(e: &[f64]) -> Expansion {
+    // From the largest component down, then back up, merging components that overlap
+    let mut down: Vec<f64> = Vec::with_capacity(e.len());
+    let mut q = match e.last() {
+        Some(&q) => q,
+        None => return Vec::new(),
+    };
+
+    for &component in e[..e.len() - 1].iter().rev() {
+        let (sum, error) = two_sum(q, component);
+        if error != 0. {
+            down.push(sum);
+            q = error;
+        } else {
+            q = sum;
+        }
+    }
+    down.push(q);
+
+    let mut out = Vec::with_capacity(down.len());
+    let mut q = down.pop().unwrap_or(0.);
+
+    while let Some(component) = down.pop() {
+        let (sum, error) = two_sum(component, q);
+        if error != 0. {
+            out.push(error);
+        }
+        q = sum;
+    }
+
+    out.push(q);
+    out
+}
+
+/// Value of an expansion rounded to one `f64`, with the exact sign.
+fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
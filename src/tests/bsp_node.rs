@@ -0,0 +1,199 @@
+use super::{assert_close, volume};
+use dim3::{BspNode, Csg, Polygon, ThreadProfile, Vector, Vertex};
+use std::thread;
+use {CsgOptions, SplitHeuristic, Unit, EPSILON};
+
+/// Squares stacked along Z, each in front of the one before. Splitting on the first polygon
+/// gives a tree as deep as the stack.
+fn stacked_squares(count: usize) -> Vec<Polygon> {
+    let normal = Vector(0., 0., 1.);
+
+    (0..count)
+        .map(|i| {
+            let z = i as Unit * 0.01;
+            Polygon::new(vec![
+                Vertex::new(Vector(0., 0., z), normal),
+                Vertex::new(Vector(1., 0., z), normal),
+                Vertex::new(Vector(1., 1., z), normal),
+                Vertex::new(Vector(0., 1., z), normal),
+            ])
+        })
+        .collect()
+}
+
+/// Far more nodes deep than a recursive traversal fits in a 64 KiB stack.
+#[test]
+fn deep_tree_on_small_stack() {
+    let worker = thread::Builder::new().stack_size(64 * 1024).spawn(|| {
+        let count = 2000;
+        let mut tree = BspNode::new(Some(stacked_squares(count)));
+        let mut copy = tree.clone();
+
+        tree.invert();
+        copy.clip_to(&tree);
+        tree.invert();
+
+        (tree.all_polygons().len(), copy.all_polygons().len())
+    });
+
+    // Only the top square has nothing above it to keep it out of the inverted tree
+    assert_eq!((2000, 1999), worker.unwrap().join().unwrap());
+}
+
+#[test]
+fn subtract_internal_thread() {
+    let nut = Csg::cylinder(Vector(0., 0., 0.), Vector(0., 0., 5.), 5., 5., 16);
+    let tool = Csg::internal_thread(1., 6., 4.9, 5., ThreadProfile::Square, 16, 0.2);
+
+    let core = Csg::cylinder(Vector(0., 0., 0.), Vector(0., 0., 5.), 2.5, 2.5, 16);
+
+    let result = volume(&Csg::subtract(&nut, &tool));
+    assert!(result > 0.);
+    assert!(result < volume(&nut) - volume(&core));
+}
+
+fn with_split(split: SplitHeuristic) -> CsgOptions {
+    CsgOptions {
+        split,
+        ..CsgOptions::default()
+    }
+}
+
+/// Separate cubes on a 4x4x4 grid.
+fn cube_grid() -> Vec<Polygon> {
+    let mut polygons = Vec::new();
+    for i in 0..64 {
+        let offset = Vector((i % 4) as Unit, (i / 4 % 4) as Unit, (i / 16) as Unit) * 2.;
+        polygons.append(
+            &mut Csg::cube(Vector(1., 1., 1.), false)
+                .translate(offset)
+                .polygons,
+        );
+    }
+    polygons
+}
+
+#[test]
+fn split_heuristics() {
+    let sphere = Csg::sphere(1., 16, 8);
+    let first = BspNode::with_options(
+        Some(sphere.polygons.clone()),
+        with_split(SplitHeuristic::First),
+    );
+    let fewest = BspNode::with_options(
+        Some(sphere.polygons.clone()),
+        with_split(SplitHeuristic::MinimizeSplits),
+    );
+    let sampled = BspNode::with_options(
+        Some(sphere.polygons.clone()),
+        with_split(SplitHeuristic::RandomSample(8)),
+    );
+    assert!(fewest.stats().splits < first.stats().splits);
+    assert!(sampled.stats().splits < first.stats().splits);
+    assert_eq!(fewest.stats().polygons, fewest.all_polygons().len());
+
+    let first = BspNode::with_options(Some(cube_grid()), with_split(SplitHeuristic::First));
+    let balanced = BspNode::with_options(Some(cube_grid()), with_split(SplitHeuristic::Balance));
+    assert!(balanced.stats().depth < first.stats().depth);
+}
+
+/// A triangle on the XY plane, then one leaning through it with corners `lean` off the plane.
+fn leaning_triangles(lean: Unit) -> Vec<Polygon> {
+    let normal = Vector(0., 0., 1.);
+    vec![
+        Polygon::new(vec![
+            Vertex::new(Vector(0., 0., 0.), normal),
+            Vertex::new(Vector(1., 0., 0.), normal),
+            Vertex::new(Vector(0., 1., 0.), normal),
+        ]),
+        Polygon::new(vec![
+            Vertex::new(Vector(0., 0., -lean), normal),
+            Vertex::new(Vector(1., 0., lean), normal),
+            Vertex::new(Vector(0., 1., -lean), normal),
+        ]),
+    ]
+}
+
+/// Polygons are counted as split when pieces land on both sides, decided exactly in robust mode.
+#[test]
+fn split_counts() {
+    let robust = CsgOptions {
+        robust: true,
+        ..with_split(SplitHeuristic::First)
+    };
+    let count = |lean: Unit, options: CsgOptions| {
+        BspNode::with_options(Some(leaning_triangles(lean)), options)
+            .stats()
+            .splits
+    };
+
+    assert_eq!(1, count(1., with_split(SplitHeuristic::First)));
+    assert_eq!(1, count(1., robust));
+
+    // Within the tolerance the second triangle lies on the plane, unless sides are exact
+    assert_eq!(0, count(EPSILON / 4., with_split(SplitHeuristic::First)));
+    assert_eq!(1, count(EPSILON / 4., robust));
+}
+
+#[test]
+fn split_heuristics_agree() {
+    let sphere = Csg::sphere(1., 16, 8);
+    let cube = Csg::cube(Vector(1.2, 1.2, 1.2), true).rotate(Vector(1., 1., 0.), 20.);
+    let expected = volume(&Csg::subtract(&sphere, &cube));
+
+    for &split in &[
+        SplitHeuristic::RandomSample(8),
+        SplitHeuristic::MinimizeSplits,
+        SplitHeuristic::Balance,
+    ] {
+        let result = Csg::subtract_with(&sphere, &cube, &with_split(split));
+        assert_close(expected, volume(&result), 0.0001);
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn positions(polygons: &[Polygon]) -> Vec<(Unit, Unit, Unit)> {
+    polygons
+        .iter()
+        .flat_map(|poly| poly.vertices.iter())
+        .map(|v| (v.position.0, v.position.1, v.position.2))
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+fn clip_serial(node: &mut BspNode, bsp: &BspNode) {
+    node.polygons = bsp.clip_polygons_serial(node.polygons.clone());
+    for child in node.front.iter_mut().chain(node.back.iter_mut()) {
+        clip_serial(child, bsp);
+    }
+}
+
+/// Building and clipping in parallel gives exactly the serial result, in the same order.
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_matches_serial() {
+    let sphere = Csg::sphere(1.5, 32, 16).polygons;
+    let grid = cube_grid();
+
+    let tree = BspNode::new(Some(sphere.clone()));
+    let mut serial = BspNode::new(None);
+    serial.build_serial(sphere);
+    assert_eq!(
+        positions(&serial.all_polygons()),
+        positions(&tree.all_polygons())
+    );
+
+    assert_eq!(
+        positions(&tree.clip_polygons_serial(grid.clone())),
+        positions(&tree.clip_polygons(&grid))
+    );
+
+    let mut parallel = BspNode::new(Some(grid.clone()));
+    let mut serial = BspNode::new(Some(grid));
+    parallel.clip_to(&tree);
+    clip_serial(&mut serial, &tree);
+    assert_eq!(
+        positions(&serial.all_polygons()),
+        positions(&parallel.all_polygons())
+    );
+}
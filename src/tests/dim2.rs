@@ -1,7 +1,147 @@
-use dim2::{circle, rectangle, Point};
+use super::area;
+use dim2::{circle, rectangle, signed_area, triangulate, Csg, Point};
+use {CsgOptions, Unit, EPSILON};
 
 #[test]
 fn dim2_shapes() {
-    let _r = rectangle(Point(0f32, 0f32), Point(4f32, 3f32));
-    let _s = circle(Point(0f32, 0f32), 1f32, 8);
+    let _r = rectangle(Point(0., 0.), Point(4., 3.));
+    let _s = circle(Point(0., 0.), 1., 8);
+}
+
+#[test]
+fn triangulate_concave() {
+    // Arrow head pointing right, wound clockwise
+    let points = [
+        Point(0., 0.),
+        Point(-1., 1.),
+        Point(2., 0.),
+        Point(-1., -1.),
+    ];
+
+    let triangles = triangulate(&points);
+    assert_eq!(2, triangles.len());
+
+    let area: Unit = triangles
+        .iter()
+        .map(|t| signed_area(&[points[t[0]], points[t[1]], points[t[2]]]))
+        .sum();
+    assert!((area - signed_area(&points)).abs() < 0.0001);
+    assert!(area < 0.);
+}
+
+#[test]
+fn regions_with_hole() {
+    let mut lines = rectangle(Point(-2., -2.), Point(2., 2.)).to_lines();
+    lines.append(&mut circle(Point(0., 0.), 1., 16).to_lines());
+    let regions = Csg::from_lines(lines).to_regions();
+
+    assert_eq!(1, regions.len());
+    assert_eq!(1, regions[0].holes.len());
+    assert!(signed_area(&regions[0].outline) > 0.);
+    assert!(signed_area(&regions[0].holes[0]) < 0.);
+
+    let points = regions[0].points();
+    let area: Unit = regions[0]
+        .triangulate()
+        .iter()
+        .map(|t| signed_area(&[points[t[0]], points[t[1]], points[t[2]]]))
+        .sum();
+    let hole_area = 16. * (2. * ::UNIT_PI / 16.).sin();
+    assert!((area - (2. * 16. - hole_area)).abs() < 0.001);
+}
+
+#[test]
+fn rectangle_booleans() {
+    let a = rectangle(Point(0., 0.), Point(2., 2.));
+    let b = rectangle(Point(1., 1.), Point(3., 3.));
+
+    assert!((area(&Csg::union(&a, &b)) - 7.).abs() < 0.0001);
+    assert!((area(&Csg::subtract(&a, &b)) - 3.).abs() < 0.0001);
+    assert!((area(&Csg::intersect(&a, &b)) - 1.).abs() < 0.0001);
+}
+
+#[test]
+fn booleans_cull_by_bounds() {
+    let big = rectangle(Point(-5., -5.), Point(5., 5.));
+    let small = rectangle(Point(-0.5, -0.5), Point(0.5, 0.5));
+
+    assert!((area(&Csg::union(&big, &small)) - 100.).abs() < 0.0001);
+    assert!((area(&Csg::subtract(&big, &small)) - 99.).abs() < 0.0001);
+    assert!((area(&Csg::intersect(&big, &small)) - 1.).abs() < 0.0001);
+
+    let apart = small.translate(Point(10., 0.));
+    assert_eq!(8, Csg::union(&big, &apart).lines.len());
+    assert_eq!(4, Csg::subtract(&big, &apart).lines.len());
+    assert_eq!(0, Csg::intersect(&big, &apart).lines.len());
+}
+
+#[test]
+fn dim2_csg_bounds() {
+    let a = rectangle(Point(0., 1.), Point(2., 5.)).bounds().unwrap();
+    let b = rectangle(Point(1., 0.), Point(4., 2.)).bounds().unwrap();
+
+    assert_eq!((2., 4.), (a.size().0, a.size().1));
+    assert_eq!((1., 3.), (a.center().0, a.center().1));
+    assert!(a.contains(Point(2., 5.)));
+    assert!(!a.contains(Point(3., 3.)));
+
+    let both = a.union(&b);
+    assert_eq!((0., 0.), (both.min.0, both.min.1));
+    assert_eq!((4., 5.), (both.max.0, both.max.1));
+
+    let shared = a.intersection(&b).unwrap();
+    assert_eq!((1., 1.), (shared.min.0, shared.min.1));
+    assert_eq!((2., 2.), (shared.max.0, shared.max.1));
+
+    let far = rectangle(Point(5., 5.), Point(6., 6.)).bounds().unwrap();
+    assert!(!b.overlaps(&far));
+    assert!(b.intersection(&far).is_none());
+}
+
+#[test]
+fn mirror_keeps_shape_outwards() {
+    let a = rectangle(Point(0., 0.), Point(2., 1.));
+
+    let mirrored = a.clone().mirror(Point(1., 0.));
+    assert!((area(&mirrored) - 2.).abs() < 0.0001);
+
+    let scaled = a.clone().scale(Point(1., -1.));
+    assert!((area(&scaled) - 2.).abs() < 0.0001);
+
+    let b = rectangle(Point(-1., -1.), Point(1., 1.));
+    assert!((area(&Csg::union(&mirrored, &b)) - 5.).abs() < 0.0001);
+}
+
+#[test]
+fn booleans_with_options() {
+    let options = CsgOptions {
+        epsilon: EPSILON / 1000.,
+        ..CsgOptions::default()
+    };
+    let a = rectangle(Point(0., 0.), Point(2., 2.) * EPSILON);
+    let b = rectangle(Point(1., 1.) * EPSILON, Point(3., 3.) * EPSILON);
+    let unit = Point(1., 1.) / EPSILON;
+
+    let union = Csg::union_with(&a, &b, &options).scale(unit);
+    assert!((area(&union) - 7.).abs() < 0.0001);
+}
+
+/// Touching squares rotated and moved where `f32` rounding bends their shared side.
+#[test]
+fn robust_union_of_touching_squares() {
+    let options = CsgOptions {
+        robust: true,
+        ..CsgOptions::default()
+    };
+    let place = |csg: Csg| {
+        csg.transform_points(|p| Point(0.8 * p.0 - 0.6 * p.1 + 1000., 0.6 * p.0 + 0.8 * p.1 + 700.))
+    };
+
+    let a = place(rectangle(Point(0., 0.), Point(1., 1.)));
+    let b = place(rectangle(Point(1., 0.), Point(2., 1.)));
+    let c = place(rectangle(Point(0.5, 0.5), Point(1.5, 1.5)));
+    let result = Csg::union_with(&Csg::union_with(&a, &b, &options), &c, &options);
+
+    let result = result.translate(Point(-1000., -700.));
+    assert!((area(&result) - 2.5).abs() < 0.001);
 }
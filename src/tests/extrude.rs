@@ -0,0 +1,178 @@
+use super::{assert_close, volume, BoundBox};
+use dim2::{circle, rectangle, Csg as Csg2, Point};
+use dim3::{Csg, Matrix4, Vector};
+use Unit;
+
+/// Square with a square hole, as one 2D shape.
+fn frame() -> Csg2 {
+    let mut lines = rectangle(Point(-2., -2.), Point(2., 2.)).to_lines();
+    lines.append(&mut rectangle(Point(-1., -1.), Point(1., 1.)).to_lines());
+    Csg2::from_lines(lines)
+}
+
+#[test]
+fn linear_extrude_rectangle() {
+    let shape = rectangle(Point(0., 0.), Point(2., 3.));
+    let solid = Csg::linear_extrude(&shape, 4., 0., 1, Point(1., 1.), false);
+    assert_close(24., volume(&solid), 0.0001);
+
+    let bb = BoundBox::from_csg(&Csg::linear_extrude(&shape, 4., 0., 1, Point(1., 1.), true));
+    let (d_min, d_max) = bb.get_min_max_discreet(10.);
+    assert_eq!((0, 0, -20), (d_min.0, d_min.1, d_min.2));
+    assert_eq!((20, 30, 20), (d_max.0, d_max.1, d_max.2));
+}
+
+#[test]
+fn linear_extrude_hole() {
+    let solid = Csg::linear_extrude(&frame(), 2., 0., 1, Point(1., 1.), false);
+    assert_close(2. * (16. - 4.), volume(&solid), 0.0001);
+}
+
+#[test]
+fn linear_extrude_twist_and_taper() {
+    let shape = circle(Point(0., 0.), 1., 32);
+    let area = 0.5 * 32. * (2. * ::UNIT_PI / 32.).sin();
+
+    let twisted = Csg::linear_extrude(&shape, 3., 90., 16, Point(1., 1.), false);
+    assert_close(3. * area, volume(&twisted), 0.05);
+
+    // Tapering to a point makes a cone
+    let cone = Csg::linear_extrude(&shape, 3., 0., 1, Point(0., 0.), false);
+    assert_close(area, volume(&cone), 0.0001);
+
+    let flared: Unit = volume(&Csg::linear_extrude(
+        &shape,
+        3.,
+        45.,
+        8,
+        Point(2., 2.),
+        true,
+    ));
+    assert_close(3. * area * 7. / 3., flared, 0.05);
+}
+
+#[test]
+fn rotate_extrude_ring() {
+    let n = 32;
+    let factor = 0.5 * (n as Unit) * (2. * ::UNIT_PI / (n as Unit)).sin();
+
+    let ring = Csg::rotate_extrude(&rectangle(Point(1., 0.), Point(2., 1.)), 360., n);
+    assert_close(factor * (4. - 1.), volume(&ring), 0.0001);
+
+    let half = Csg::rotate_extrude(&rectangle(Point(1., 0.), Point(2., 1.)), 180., n / 2);
+    assert_close(factor * (4. - 1.) / 2., volume(&half), 0.0001);
+
+    // Clockwise sweeps face outwards too
+    let clockwise = Csg::rotate_extrude(&rectangle(Point(1., 0.), Point(2., 1.)), -180., n / 2);
+    assert_close(factor * (4. - 1.) / 2., volume(&clockwise), 0.0001);
+
+    let clockwise_ring = Csg::rotate_extrude(&rectangle(Point(1., 0.), Point(2., 1.)), -360., n);
+    assert_close(factor * (4. - 1.), volume(&clockwise_ring), 0.0001);
+}
+
+#[test]
+fn rotate_extrude_touching_axis() {
+    let n = 24;
+    let factor = 0.5 * (n as Unit) * (2. * ::UNIT_PI / (n as Unit)).sin();
+
+    let cylinder = Csg::rotate_extrude(&rectangle(Point(0., 0.), Point(1., 2.)), 360., n);
+    assert_close(factor * 2., volume(&cylinder), 0.0001);
+
+    // The edge on the axis collapses, no polygon may degenerate into a line
+    for poly in &cylinder.polygons {
+        assert!(poly.vertices.len() >= 3);
+        assert_close(1., poly.plane.0.length(), 0.0001);
+    }
+
+    let wedge = Csg::rotate_extrude(&rectangle(Point(0., 0.), Point(1., 2.)), 90., 6);
+    let wedge_factor = 0.5 * 6. * (::UNIT_PI / 12.).sin();
+    assert_close(wedge_factor * 2., volume(&wedge), 0.0001);
+}
+
+#[test]
+fn sweep_repeated_points() {
+    let shape = rectangle(Point(-0.5, -0.5), Point(0.5, 0.5));
+    let path = [
+        Vector(0., 0., 0.),
+        Vector(0., 0., 1.),
+        Vector(0., 0., 1.),
+        Vector(0., 0., 2.),
+    ];
+    let solid = Csg::sweep(&shape, &path);
+    let plain = Csg::sweep(&shape, &[path[0], path[1], path[3]]);
+
+    assert_eq!(plain.polygons.len(), solid.polygons.len());
+    assert_close(2., volume(&solid), 0.0001);
+    assert_eq!(0, Csg::sweep(&shape, &[path[1], path[2]]).polygons.len());
+}
+
+#[test]
+fn sweep_straight() {
+    let shape = rectangle(Point(-1., -0.5), Point(1., 0.5));
+    let path = [Vector(0., 0., 0.), Vector(0., 0., 1.), Vector(0., 0., 3.)];
+    let solid = Csg::sweep(&shape, &path);
+    assert_close(6., volume(&solid), 0.0001);
+
+    // Same placement as a linear extrusion
+    let bb = BoundBox::from_csg(&solid);
+    let (d_min, d_max) = bb.get_min_max_discreet(10.);
+    assert_eq!((-10, -5, 0), (d_min.0, d_min.1, d_min.2));
+    assert_eq!((10, 5, 30), (d_max.0, d_max.1, d_max.2));
+}
+
+#[test]
+fn sweep_planar_bend_does_not_twist() {
+    // Quarter circle in the XZ plane, the profile must stay flat against Y
+    let steps = 16;
+    let path: Vec<Vector> = (0..=steps)
+        .map(|i| {
+            let angle = (i as Unit) / (steps as Unit) * ::UNIT_PI * 0.5;
+            Vector(5. - 5. * angle.cos(), 0., 5. * angle.sin())
+        })
+        .collect();
+
+    let shape = rectangle(Point(-0.5, -1.), Point(0.5, 1.));
+    let solid = Csg::sweep(&shape, &path);
+
+    for poly in &solid.polygons {
+        for v in &poly.vertices {
+            assert_close(1., v.position.1.abs(), 0.0001);
+        }
+    }
+
+    // Pappus, length of the path times the area of the shape
+    let length: Unit = path.windows(2).map(|w| (w[1] - w[0]).length()).sum();
+    assert_close(2. * length, volume(&solid), 0.05);
+}
+
+#[test]
+fn loft_round_to_square() {
+    let sections = vec![
+        (circle(Point(0., 0.), 1., 32), Matrix4::identity()),
+        (
+            rectangle(Point(-1., -1.), Point(1., 1.)),
+            Matrix4::translation(Vector(0., 0., 2.)),
+        ),
+    ];
+
+    let duct = Csg::loft(&sections);
+    let bb = BoundBox::from_csg(&duct);
+    let (d_min, d_max) = bb.get_min_max_discreet(10.);
+    assert_eq!((-10, -10, 0), (d_min.0, d_min.1, d_min.2));
+    assert_eq!((10, 10, 20), (d_max.0, d_max.1, d_max.2));
+
+    // Between a prism of the circle and one of the square
+    let v = volume(&duct);
+    assert!(v > 2. * ::UNIT_PI * 0.99 && v < 8.);
+}
+
+#[test]
+fn loft_stacked_downwards() {
+    let shape = rectangle(Point(0., 0.), Point(2., 3.));
+    // The solid must face outwards even though the sections go against their Z axis
+    let down = vec![
+        (shape.clone(), Matrix4::identity()),
+        (shape.clone(), Matrix4::translation(Vector(0., 0., -4.))),
+    ];
+    assert_close(24., volume(&Csg::loft(&down)), 0.0001);
+}
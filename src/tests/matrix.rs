@@ -0,0 +1,125 @@
+use super::{assert_close, assert_vector_close, volume};
+use dim3::{Csg, Matrix4, Vector};
+
+#[test]
+fn compose_and_invert() {
+    let m = Matrix4::translation(Vector(1., 2., 3.))
+        * Matrix4::rotation(Vector(1., 1., 0.), 30.)
+        * Matrix4::scale(Vector(2., 3., 4.));
+
+    let p = Vector(0.5, -1., 2.);
+    let inverse = m.inverse().unwrap();
+    assert_vector_close(p, inverse.transform_point(m.transform_point(p)));
+    assert_vector_close(p, (m * inverse).transform_point(p));
+
+    assert!(Matrix4::scale(Vector(1., 0., 1.)).inverse().is_none());
+}
+
+#[test]
+fn rotation_matches_vector_rotate() {
+    let axis = Vector(1., 2., 3.).normalize();
+    let v = Vector(3., -1., 2.);
+
+    assert_vector_close(
+        v.rotate(axis, 40.),
+        Matrix4::rotation(axis * 5., 40.).transform_point(v),
+    );
+}
+
+#[test]
+fn look_at() {
+    let eye = Vector(1., 2., 3.);
+    let view = Matrix4::look_at(eye, Vector(1., 2., -7.), Vector(0., 1., 0.));
+
+    assert_vector_close(Vector(0., 0., 0.), view.transform_point(eye));
+    assert_vector_close(
+        Vector(0., 0., -10.),
+        view.transform_point(Vector(1., 2., -7.)),
+    );
+    assert_vector_close(
+        Vector(0., 1., 0.),
+        view.transform_vector(Vector(0., 1., 0.)),
+    );
+}
+
+#[test]
+fn transform_csg() {
+    let m = Matrix4::translation(Vector(5., 0., 0.)) * Matrix4::scale(Vector(1., 2., 3.));
+    let cube = Csg::cube(Vector(1., 1., 1.), true).transform(&m);
+    assert_close(6., volume(&cube), 0.0001);
+
+    for poly in &cube.polygons {
+        for v in &poly.vertices {
+            assert_vector_close(poly.plane.0, v.normal);
+            assert_close(poly.plane.1, poly.plane.0.dot(v.position), 0.0001);
+        }
+    }
+
+    // One pass gives the same result as chained transformations
+    let chained = Csg::cube(Vector(1., 1., 1.), true)
+        .scale(Vector(1., 2., 3.))
+        .translate(Vector(5., 0., 0.));
+    for (a, b) in cube.polygons.iter().zip(chained.polygons.iter()) {
+        for (va, vb) in a.vertices.iter().zip(b.vertices.iter()) {
+            assert_vector_close(vb.position, va.position);
+        }
+    }
+}
+
+#[test]
+fn mirror_keeps_solid_outwards() {
+    let cube = Csg::cube(Vector(1., 2., 3.), false);
+
+    let mirrored = cube.clone().mirror(Vector(1., 1., 0.));
+    assert_close(6., volume(&mirrored), 0.0001);
+
+    let scaled = cube.clone().scale(Vector(-1., 1., 1.));
+    assert_close(6., volume(&scaled), 0.0001);
+
+    let transformed = cube.clone().transform(&Matrix4::scale(Vector(1., -2., 1.)));
+    assert_close(12., volume(&transformed), 0.0001);
+
+    for poly in mirrored.polygons.iter().chain(transformed.polygons.iter()) {
+        for v in &poly.vertices {
+            assert_vector_close(poly.plane.0, v.normal);
+        }
+    }
+
+    // Booleans keep working on mirrored solids
+    let other = Csg::cube(Vector(1., 1., 1.), false).translate(Vector(-0.5, 0., 0.));
+    assert_close(6.5, volume(&Csg::union(&scaled, &other)), 0.0001);
+}
+
+#[test]
+fn scaled_sphere_normals() {
+    let s = Vector(1., 2., 3.);
+    let ellipsoid = Csg::sphere(1., 16, 8).scale(s);
+
+    // Gradient of x²/a² + y²/b² + z²/c²
+    for poly in &ellipsoid.polygons {
+        for vert in &poly.vertices {
+            let p = vert.position;
+            let expected = Vector(p.0 / (s.0 * s.0), p.1 / (s.1 * s.1), p.2 / (s.2 * s.2));
+            assert_vector_close(expected.normalize(), vert.normal);
+        }
+    }
+}
+
+#[test]
+fn rotate_and_translate_keep_normals_unit() {
+    let sphere = Csg::sphere(1., 16, 8)
+        .scale(Vector(2., 1., 1.))
+        .rotate(Vector(0., 0., 1.), 90.)
+        .translate(Vector(1., 2., 3.));
+
+    for poly in &sphere.polygons {
+        for vert in &poly.vertices {
+            assert_close(1., vert.normal.length(), 0.0001);
+
+            // The long axis now lies along y
+            let p = vert.position - Vector(1., 2., 3.);
+            let expected = Vector(p.0, p.1 / 4., p.2).normalize();
+            assert_vector_close(expected, vert.normal);
+        }
+    }
+}
@@ -1,9 +1,68 @@
 mod bounding_box;
+mod bsp_node;
 mod dim2;
+mod extrude;
+mod matrix;
 mod plane;
+mod predicates;
+mod primitives;
+mod project;
+mod quaternion;
+mod thread;
 
 use self::bounding_box::BoundBox;
 use dim3::{BspNode, Csg, Plane, Polygon, Vector, Vertex};
+use {CsgOptions, Unit, EPSILON};
+
+/// Signed volume of a closed mesh, negative when polygons face inwards. Measured from a vertex
+/// of the mesh so models far from origo keep their precision.
+pub fn volume(csg: &Csg) -> Unit {
+    let origin = match csg.polygons.first() {
+        Some(poly) => poly.vertices[0].position,
+        None => return 0.,
+    };
+    let mut sum = 0.;
+    csg.iter_triangles(|tri| {
+        let [a, b, c] = tri.positions;
+        sum += (a - origin).dot((b - origin).cross(c - origin)) / 6.;
+    });
+    sum
+}
+
+/// Area enclosed by clockwise outlines.
+pub fn area(csg: &::dim2::Csg) -> Unit {
+    csg.lines
+        .iter()
+        .map(|l| -(l.p0.0 * l.p1.1 - l.p1.0 * l.p0.1) * 0.5)
+        .sum()
+}
+
+/// True if `a` and `b` have exactly the same vertices in the same order.
+fn same(a: &Polygon, b: &Polygon) -> bool {
+    a.vertices.len() == b.vertices.len()
+        && a.vertices
+            .iter()
+            .zip(&b.vertices)
+            .all(|(u, v)| (u.position - v.position).length() == 0.)
+}
+
+pub fn assert_close(expected: Unit, actual: Unit, tolerance: Unit) {
+    assert!(
+        (expected - actual).abs() < tolerance,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+pub fn assert_vector_close(expected: Vector, actual: Vector) {
+    assert!(
+        (expected - actual).length() < 0.0001,
+        "expected {:?}, got {:?}",
+        expected,
+        actual
+    );
+}
 
 #[test]
 fn types() {
@@ -36,6 +95,52 @@ fn csg_cube() {
     assert_eq!(10, d_max.2);
 }
 
+#[test]
+fn csg_bounds() {
+    let a = Csg::cube(Vector(2., 4., 6.), false)
+        .translate(Vector(1., 0., -1.))
+        .bounds()
+        .unwrap();
+    let b = Csg::cube(Vector(2., 2., 2.), true).bounds().unwrap();
+
+    assert_eq!((2., 4., 6.), (a.size().0, a.size().1, a.size().2));
+    assert_eq!((2., 2., 2.), (a.center().0, a.center().1, a.center().2));
+    assert!(a.contains(Vector(3., 4., 5.)));
+    assert!(!a.contains(Vector(0., 0., 0.)));
+    assert!(a.overlaps(&b));
+
+    let both = a.union(&b);
+    assert_eq!((-1., -1., -1.), (both.min.0, both.min.1, both.min.2));
+    assert_eq!((3., 4., 5.), (both.max.0, both.max.1, both.max.2));
+
+    let shared = a.intersection(&b).unwrap();
+    assert_eq!((1., 0., -1.), (shared.min.0, shared.min.1, shared.min.2));
+    assert_eq!((1., 1., 1.), (shared.max.0, shared.max.1, shared.max.2));
+
+    let far = Csg::cube(Vector(1., 1., 1.), false)
+        .translate(Vector(5., 0., 0.))
+        .bounds()
+        .unwrap();
+    assert!(!b.overlaps(&far));
+    assert!(b.intersection(&far).is_none());
+    assert!(Csg::new().bounds().is_none());
+}
+
+/// All vertices of the first polygon count, not only its first one.
+#[test]
+fn bounds_single_triangle() {
+    let normal = Vector(0., 0., 1.);
+    let triangle = Polygon::new(vec![
+        Vertex::new(Vector(1., 1., 0.), normal),
+        Vertex::new(Vector(3., 1., 0.), normal),
+        Vertex::new(Vector(1., 2., 0.), normal),
+    ]);
+
+    let bb = Csg::from_polygons(vec![triangle]).bounds().unwrap();
+    assert_eq!((1., 1., 0.), (bb.min.0, bb.min.1, bb.min.2));
+    assert_eq!((3., 2., 0.), (bb.max.0, bb.max.1, bb.max.2));
+}
+
 /// Big cube will subtract itself onto a smaller cube, removing everything.
 #[test]
 fn csg_total_subtraction() {
@@ -48,6 +153,90 @@ fn csg_total_subtraction() {
     assert_eq!(0, polys.len());
 }
 
+/// Faces outside the bounds of the other solid skip clipping, yet still count as part of it.
+#[test]
+fn booleans_cull_by_bounds() {
+    let big = Csg::cube(Vector(10., 10., 10.), true);
+    let small = Csg::cube(Vector(1., 1., 1.), true);
+
+    // No face of the big cube comes near the small one, containment decides what is kept
+    assert_close(1000., volume(&Csg::union(&big, &small)), 0.01);
+    assert_close(999., volume(&Csg::subtract(&big, &small)), 0.01);
+    assert_close(1., volume(&Csg::intersect(&big, &small)), 0.001);
+
+    // The three faces away from the corner are not split by the planes of the small cube
+    let corner = small.clone().translate(Vector(5., 5., 5.));
+    let union = Csg::union(&big, &corner);
+    assert_close(1000. + 7. / 8., volume(&union), 0.01);
+
+    let kept = big
+        .polygons
+        .iter()
+        .filter(|poly| union.polygons.iter().any(|other| same(poly, other)))
+        .count();
+    assert_eq!(3, kept);
+
+    let apart = small.clone().translate(Vector(10., 0., 0.));
+    assert_eq!(12, Csg::union(&big, &apart).polygons.len());
+    assert_eq!(6, Csg::subtract(&big, &apart).polygons.len());
+    assert_eq!(0, Csg::intersect(&big, &apart).polygons.len());
+}
+
+/// Only faces near the other solid go into the BSP trees, so those of a detailed model away from
+/// a small part added to it are not cut by the planes of the part.
+#[test]
+fn booleans_skip_far_faces() {
+    let sphere = Csg::sphere(10., 32, 16);
+    let cube = Csg::cube(Vector(1., 1., 1.), true).translate(Vector(10., 0., 0.));
+    let union = Csg::union(&sphere, &cube);
+
+    let far: Vec<&Polygon> = sphere
+        .polygons
+        .iter()
+        .filter(|poly| poly.vertices.iter().all(|v| v.position.0 < 9.))
+        .collect();
+    let across = far
+        .iter()
+        .filter(|poly| {
+            let ys = poly.vertices.iter().map(|v| v.position.1);
+            ys.clone().any(|y| y < 0.5) && ys.clone().any(|y| y > 0.5)
+        })
+        .count();
+    assert!(across > 0);
+    for poly in far {
+        assert!(union.polygons.iter().any(|other| same(poly, other)));
+    }
+
+    let inside = volume(&Csg::intersect(&sphere, &cube));
+    assert_close(
+        volume(&sphere) + volume(&cube) - inside,
+        volume(&union),
+        0.01,
+    );
+}
+
+/// Containment of a solid with no faces near the other does not depend on how the faces meet.
+/// The ray of a parity test from the small cube would pass through the diagonal of a face of the
+/// triangulated one.
+#[test]
+fn booleans_contain_across_edges() {
+    let mut polygons = Vec::new();
+    let cube = Csg::cube(Vector(3., 3., 3.), true).translate(Vector(0., 0.6875, -0.5));
+    for poly in cube.polygons {
+        let v = &poly.vertices;
+        polygons.push(Polygon::new(vec![v[0], v[1], v[2]]));
+        polygons.push(Polygon::new(vec![v[0], v[2], v[3]]));
+    }
+    let big = Csg::from_polygons(polygons);
+    let small = Csg::cube(Vector(1., 1., 1.), true);
+
+    let union = Csg::union(&big, &small);
+    assert_eq!(12, union.polygons.len());
+    assert_close(27., volume(&union), 0.001);
+    assert_close(26., volume(&Csg::subtract(&big, &small)), 0.001);
+    assert_close(1., volume(&Csg::intersect(&big, &small)), 0.001);
+}
+
 #[test]
 fn csg_sphere() {
     let sphere = Csg::sphere(1.0, 10, 5);
@@ -62,3 +251,46 @@ fn csg_sphere() {
     assert_eq!(10, d_max.1);
     assert_eq!(10, d_max.2);
 }
+
+/// Octahedron poking out through all six faces of a cube, leaving the cube corners.
+#[test]
+fn csg_cube_subtract_octahedron() {
+    let cube = Csg::cube(Vector(1., 1., 1.), true);
+    let octahedron = Csg::octahedron(0.8);
+    let result = Csg::subtract(&cube, &octahedron);
+
+    // Octahedron volume minus the six tips outside the cube
+    let tip = 2. * 0.3 * 0.3 * 0.3 / 3.;
+    let inside = 4. / 3. * 0.8 * 0.8 * 0.8 - 6. * tip;
+    assert_close(1. - inside, volume(&result), 0.0001);
+}
+
+/// Millimeter sized parts placed meters from origo.
+#[cfg(feature = "f64")]
+#[test]
+fn f64_far_from_origo() {
+    let offset = Vector(5000., 5000., 5000.);
+    let cube = Csg::cube(Vector(1., 1., 1.), false).translate(offset);
+    let corner = Csg::cube(Vector(0.5, 0.5, 0.5), false).translate(offset);
+    let result = Csg::subtract(&cube, &corner);
+
+    assert_close(0.875, volume(&result), 0.000001);
+}
+
+/// Features smaller than the default tolerance need a finer one.
+#[test]
+fn csg_options_epsilon() {
+    let options = CsgOptions {
+        epsilon: EPSILON / 1000.,
+        ..CsgOptions::default()
+    };
+    let cube = Csg::cube(Vector(2., 2., 2.) * EPSILON, false);
+    let corner = Csg::cube(Vector(1., 1., 1.) * EPSILON, false);
+    let unit = Vector(1., 1., 1.) / EPSILON;
+
+    let result = Csg::subtract_with(&cube, &corner, &options);
+    assert_close(7., volume(&result.scale(unit)), 0.001);
+
+    let result = Csg::subtract(&cube, &corner);
+    assert!((7. - volume(&result.scale(unit))).abs() > 0.001);
+}
@@ -0,0 +1,128 @@
+use super::{assert_close, volume};
+use dim3::{Csg, Vector};
+use predicates::{orient2d, orient3d};
+use std::collections::HashMap;
+use {CsgOptions, Unit};
+
+/// Smallest step around 0.5.
+const ULP: f64 = 1. / (1u64 << 53) as f64;
+
+fn sign(x: f64) -> i128 {
+    if x > 0. {
+        1
+    } else if x < 0. {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Coordinate scaled to an exact integer.
+fn int(x: f64) -> i128 {
+    (x * (1u64 << 53) as f64) as i128
+}
+
+/// Points a few units in the last place around the line through (12, 12) and (24, 24). Plain
+/// `f64` gets almost all of these wrong.
+#[test]
+fn orient2d_near_collinear() {
+    let (q, r) = ([12., 12.], [24., 24.]);
+
+    for i in 0..16 {
+        for j in 0..16 {
+            let p = [0.5 + i as f64 * ULP, 0.5 + j as f64 * ULP];
+
+            let det = (int(p[0]) - int(r[0])) * (int(q[1]) - int(r[1]))
+                - (int(p[1]) - int(r[1])) * (int(q[0]) - int(r[0]));
+            assert_eq!(det.signum(), sign(orient2d(p, q, r)));
+        }
+    }
+}
+
+/// Same points lifted next to the vertical plane through that line.
+#[test]
+fn orient3d_near_coplanar() {
+    let (a, b, c) = ([12., 12., 0.], [24., 24., 0.], [12., 12., 1.]);
+
+    for i in 0..16 {
+        for j in 0..16 {
+            let d = [0.5 + i as f64 * ULP, 0.5 + j as f64 * ULP, 0.25];
+
+            // The plane normal is (1, -1, 0) scaled, seen from the positive side a, b, c wind
+            // counter clockwise
+            let det = (int(d[0]) - int(a[0])) - (int(d[1]) - int(a[1]));
+            assert_eq!(det.signum(), sign(orient3d(a, b, c, d)));
+        }
+    }
+}
+
+/// Three unit cubes, two touching and one overlapping both, rotated and moved where `f32`
+/// rounding leaves their faces a little off planar.
+fn touching_cubes(angle: Unit) -> (Csg, Csg, Csg) {
+    let place = |csg: Csg| {
+        csg.rotate(Vector(1., 2., 3.).normalize(), angle)
+            .translate(Vector(100., 70., 30.))
+    };
+    let cube = || Csg::cube(Vector(1., 1., 1.), false);
+
+    (
+        place(cube()),
+        place(cube().translate(Vector(1., 0., 0.))),
+        place(cube().translate(Vector(0.5, 0.5, 0.))),
+    )
+}
+
+#[test]
+fn robust_union_of_touching_cubes() {
+    let options = CsgOptions {
+        robust: true,
+        ..CsgOptions::default()
+    };
+
+    for &angle in &[0., 17., 30., 45.] {
+        let (a, b, c) = touching_cubes(angle);
+        let result = Csg::union_with(&Csg::union_with(&a, &b, &options), &c, &options);
+
+        let result = result.translate(Vector(-100., -70., -30.));
+        assert_close(2.5, volume(&result), 0.0001);
+    }
+}
+
+/// Edges of `csg` not matched by the same edge running the other way in another polygon.
+fn open_edges(csg: &Csg) -> usize {
+    let key = |v: Vector| (v.0.to_bits(), v.1.to_bits(), v.2.to_bits());
+    let mut count = HashMap::new();
+
+    for poly in &csg.polygons {
+        let n = poly.vertices.len();
+        for i in 0..n {
+            let from = key(poly.vertices[i].position);
+            let to = key(poly.vertices[(i + 1) % n].position);
+            if from != to {
+                *count.entry((from, to)).or_insert(0) += 1;
+                *count.entry((to, from)).or_insert(0) -= 1;
+            }
+        }
+    }
+
+    count.values().filter(|&&c| c != 0).count()
+}
+
+#[test]
+fn robust_booleans_of_touching_cubes_are_closed() {
+    let options = CsgOptions {
+        robust: true,
+        ..CsgOptions::default()
+    };
+
+    for &angle in &[0., 17., 30., 45.] {
+        let (a, b, c) = touching_cubes(angle);
+        let ab = Csg::union_with(&a, &b, &options);
+
+        assert_eq!(0, open_edges(&ab));
+        assert_eq!(0, open_edges(&Csg::union_with(&a, &c, &options)));
+        assert_eq!(0, open_edges(&Csg::union_with(&ab, &c, &options)));
+        assert_eq!(0, open_edges(&Csg::subtract_with(&ab, &c, &options)));
+        assert_eq!(0, open_edges(&Csg::intersect_with(&ab, &c, &options)));
+    }
+}
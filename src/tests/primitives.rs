@@ -0,0 +1,265 @@
+use super::{assert_close, volume, BoundBox};
+use dim3::{Csg, Vector};
+use Unit;
+
+#[test]
+fn cylinder_volume() {
+    let slices = 16;
+    let cylinder = Csg::cylinder(Vector(0., 0., -1.), Vector(0., 0., 1.), 1., 1., slices);
+
+    // Volume of the inscribed prism
+    let n = slices as Unit;
+    let expected = 0.5 * n * (2. * ::UNIT_PI / n).sin() * 2.;
+    assert_close(expected, volume(&cylinder), 0.001);
+}
+
+#[test]
+fn cone_volume() {
+    let slices = 32;
+    let cone = Csg::cylinder(Vector(1., 2., 3.), Vector(1., 2., 6.), 2., 0., slices);
+
+    let n = slices as Unit;
+    let base = 0.5 * n * 4. * (2. * ::UNIT_PI / n).sin();
+    let height = 3.;
+    assert_close(base * height / 3., volume(&cone), 0.001);
+}
+
+#[test]
+fn cylinder_normals() {
+    let cylinder = Csg::cylinder(Vector(0., 0., 0.), Vector(2., 0., 0.), 1., 1., 8);
+
+    for poly in &cylinder.polygons {
+        let center = poly
+            .vertices
+            .iter()
+            .fold(Vector(0., 0., 0.), |acc, v| acc + v.position)
+            / (poly.vertices.len() as Unit);
+
+        // Every face points away from the axis or out through a cap
+        let outward = Vector(center.0 - 1., center.1, center.2);
+        assert!(poly.plane.0.dot(outward) > 0.);
+
+        for v in &poly.vertices {
+            assert_close(1., v.normal.length(), 0.0001);
+        }
+    }
+}
+
+#[test]
+fn torus_volume() {
+    let torus = Csg::torus(2., 0.5, 64, 32);
+    let expected = 2. * ::UNIT_PI * ::UNIT_PI * 2. * 0.5 * 0.5;
+    assert_close(expected, volume(&torus), expected * 0.01);
+}
+
+#[test]
+fn ellipsoid_volume() {
+    let ellipsoid = Csg::ellipsoid(Vector(1., 2., 3.), 64, 32);
+    let expected = 4. / 3. * ::UNIT_PI * 1. * 2. * 3.;
+    assert_close(expected, volume(&ellipsoid), expected * 0.01);
+}
+
+#[test]
+fn ellipsoid_normals() {
+    let radii = Vector(1., 2., 3.);
+    let ellipsoid = Csg::ellipsoid(radii, 16, 8);
+
+    for poly in &ellipsoid.polygons {
+        for v in &poly.vertices {
+            let p = v.position;
+            let gradient = Vector(p.0 / 1., p.1 / 4., p.2 / 9.).normalize();
+            assert!(gradient.dot(v.normal) > 0.9999);
+        }
+    }
+}
+
+#[test]
+fn capsule_volume() {
+    let capsule = Csg::capsule(Vector(0., 0., 0.), Vector(0., 3., 0.), 1., 64, 16);
+    let expected = ::UNIT_PI * 3. + 4. / 3. * ::UNIT_PI;
+    assert_close(expected, volume(&capsule), expected * 0.01);
+}
+
+#[test]
+fn icosphere_volume() {
+    let icosahedron = Csg::icosphere(1., 0);
+    assert_eq!(20, icosahedron.polygons.len());
+
+    let icosphere = Csg::icosphere(2., 3);
+    assert_eq!(20 * 64, icosphere.polygons.len());
+
+    let expected = 4. / 3. * ::UNIT_PI * 8.;
+    assert_close(expected, volume(&icosphere), expected * 0.02);
+
+    for poly in &icosphere.polygons {
+        for v in &poly.vertices {
+            assert_close(2., v.position.length(), 0.0001);
+            assert!(poly.plane.0.dot(v.normal) > 0.9);
+        }
+    }
+}
+
+#[test]
+fn polyhedron_cube() {
+    let points = [
+        Vector(0., 0., 0.),
+        Vector(1., 0., 0.),
+        Vector(1., 1., 0.),
+        Vector(0., 1., 0.),
+        Vector(0., 0., 1.),
+        Vector(1., 0., 1.),
+        Vector(1., 1., 1.),
+        Vector(0., 1., 1.),
+    ];
+    let faces = vec![
+        vec![0, 3, 2, 1],
+        vec![4, 5, 6, 7],
+        vec![0, 1, 5, 4],
+        vec![1, 2, 6, 5],
+        vec![2, 3, 7, 6],
+        vec![3, 0, 4, 7],
+    ];
+
+    let cube = Csg::polyhedron(&points, &faces);
+    assert_eq!(6, cube.polygons.len());
+    assert_close(1., volume(&cube), 0.0001);
+}
+
+/// L shaped prism, the two end caps are concave and must be triangulated.
+#[test]
+fn polyhedron_concave() {
+    let outline = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)];
+    let mut points: Vec<Vector> = outline.iter().map(|&(x, y)| Vector(x, y, 0.)).collect();
+    points.extend(outline.iter().map(|&(x, y)| Vector(x, y, 1.)));
+
+    let n = outline.len();
+    let mut faces: Vec<Vec<usize>> = vec![(0..n).rev().collect(), (n..2 * n).collect()];
+    for i in 0..n {
+        let j = (i + 1) % n;
+        faces.push(vec![i, j, j + n, i + n]);
+    }
+
+    let prism = Csg::polyhedron(&points, &faces);
+    assert_eq!(2 * 4 + n, prism.polygons.len());
+    assert_close(3., volume(&prism), 0.0001);
+
+    for poly in &prism.polygons {
+        for v in &poly.vertices {
+            assert_close(1., v.normal.dot(poly.plane.0), 0.0001);
+        }
+    }
+}
+
+#[test]
+fn polyhedron_non_planar_face() {
+    let points = [
+        Vector(0., 0., 0.),
+        Vector(1., 0., 0.),
+        Vector(1., 1., 0.5),
+        Vector(0., 1., 0.),
+    ];
+
+    let warped = Csg::polyhedron(&points, &[vec![0, 1, 2, 3]]);
+    assert_eq!(2, warped.polygons.len());
+
+    for poly in &warped.polygons {
+        assert!(poly.plane.0 .2 > 0.);
+
+        for v in &poly.vertices {
+            assert_close(poly.plane.1, poly.plane.0.dot(v.position), 0.0001);
+        }
+    }
+}
+
+#[test]
+fn platonic_volumes() {
+    let tetrahedron = Csg::tetrahedron(1.);
+    let edge = (8. / 3. as Unit).sqrt();
+    assert_eq!(4, tetrahedron.polygons.len());
+    assert_close(
+        edge.powi(3) / (6. * (2. as Unit).sqrt()),
+        volume(&tetrahedron),
+        0.0001,
+    );
+
+    let octahedron = Csg::octahedron(1.);
+    assert_eq!(8, octahedron.polygons.len());
+    assert_close(4. / 3., volume(&octahedron), 0.0001);
+
+    let dodecahedron = Csg::dodecahedron(1.);
+    assert_eq!(12, dodecahedron.polygons.len());
+    assert_close(2.78516, volume(&dodecahedron), 0.0001);
+
+    let icosahedron = Csg::icosahedron(1.);
+    assert_eq!(20, icosahedron.polygons.len());
+    assert_close(2.53615, volume(&icosahedron), 0.0001);
+}
+
+#[test]
+fn prism_and_pyramid_volumes() {
+    let n = 6.;
+    let base = 0.5 * n * 4. * (2. * ::UNIT_PI / n).sin();
+
+    let prism = Csg::prism(6, 2., 3.);
+    assert_eq!(8, prism.polygons.len());
+    assert_close(base * 3., volume(&prism), 0.0001);
+
+    let pyramid = Csg::pyramid(6, 2., 3.);
+    assert_eq!(7, pyramid.polygons.len());
+    assert_close(base, volume(&pyramid), 0.0001);
+}
+
+#[test]
+fn rounded_cube_volume() {
+    let (a, b, c, r) = (4., 3., 2., 0.5);
+    let rounded = Csg::rounded_cube(Vector(a, b, c), r, 16, true);
+
+    let (x, y, z) = (a - 2. * r, b - 2. * r, c - 2. * r);
+    let expected = x * y * z
+        + 2. * r * (x * y + y * z + z * x)
+        + ::UNIT_PI * r * r * (x + y + z)
+        + 4. / 3. * ::UNIT_PI * r * r * r;
+    assert_close(expected, volume(&rounded), expected * 0.01);
+}
+
+#[test]
+fn rounded_cube_bounds() {
+    let rounded = Csg::rounded_cube(Vector(4., 3., 2.), 0.5, 4, false);
+    let bb = BoundBox::from_csg(&rounded);
+    let (d_min, d_max) = bb.get_min_max_discreet(10.);
+
+    assert_eq!((0, 0, 0), (d_min.0, d_min.1, d_min.2));
+    assert_eq!((40, 30, 20), (d_max.0, d_max.1, d_max.2));
+}
+
+/// A radius of half the smallest dimension leaves no flat faces along it, larger ones are clamped.
+#[test]
+fn rounded_cube_full_radius() {
+    let ball = Csg::rounded_cube(Vector(1., 1., 1.), 0.5, 4, true);
+    let clamped = Csg::rounded_cube(Vector(1., 1., 1.), 0.8, 4, true);
+    let sphere_volume = 4. / 3. * ::UNIT_PI * 0.125;
+
+    for csg in &[&ball, &clamped] {
+        assert!(csg.polygons.iter().all(|poly| poly.plane.0.length() > 0.99));
+        assert_close(sphere_volume, volume(csg), sphere_volume * 0.2);
+    }
+    assert_eq!(ball.polygons.len(), clamped.polygons.len());
+
+    let capsule = Csg::rounded_cube(Vector(3., 1., 2.), 1., 4, true);
+    assert!(capsule
+        .polygons
+        .iter()
+        .all(|poly| poly.plane.0.length() > 0.99));
+    assert!(volume(&capsule) > 0.);
+}
+
+#[test]
+fn sphere_faces_outwards() {
+    let sphere = Csg::sphere(1., 16, 8);
+    assert!(volume(&sphere) > 0.);
+
+    for poly in &sphere.polygons {
+        let center = poly.vertices[0].position;
+        assert!(poly.plane.0.dot(center) > 0.);
+    }
+}
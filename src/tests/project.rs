@@ -0,0 +1,134 @@
+use super::area;
+use dim3::{Csg, Plane, Polygon, Vector, Vertex};
+
+fn xy_plane() -> Plane {
+    Plane(Vector(0., 0., 1.), 0.)
+}
+
+#[test]
+fn project_cube() {
+    let cube = Csg::cube(Vector(2., 3., 4.), false);
+    let shadow = cube.project(&xy_plane());
+    assert!((area(&shadow) - 6.).abs() < 0.0001);
+
+    // Coordinates follow X and Y
+    for line in &shadow.lines {
+        for p in &[line.p0, line.p1] {
+            assert!(p.0 > -0.0001 && p.0 < 2.0001);
+            assert!(p.1 > -0.0001 && p.1 < 3.0001);
+        }
+    }
+
+    let side = cube.project(&Plane(Vector(1., 0., 0.), 5.));
+    assert!((area(&side) - 12.).abs() < 0.0001);
+}
+
+#[test]
+fn project_overlapping_parts() {
+    // Two boxes stacked crosswise, the shadow is a plus sign
+    let a = Csg::cube(Vector(3., 1., 1.), true);
+    let b = Csg::cube(Vector(1., 3., 1.), true).translate(Vector(0., 0., 2.));
+    let mut polygons = a.polygons;
+    polygons.append(&mut b.to_polygons());
+
+    let shadow = Csg::from_polygons(polygons).project(&xy_plane());
+    assert!((area(&shadow) - 5.).abs() < 0.0001);
+    assert_eq!(1, shadow.to_regions().len());
+}
+
+#[test]
+fn project_sphere() {
+    let shadow = Csg::icosphere(1., 2).project(&xy_plane());
+    let a = area(&shadow);
+    assert!(a > 2.8 && a < ::UNIT_PI);
+}
+
+#[test]
+fn slice_cube() {
+    let cube = Csg::cube(Vector(2., 3., 1.), false);
+
+    let middle = cube.slice(&Plane(Vector(0., 0., 1.), 0.5));
+    assert_eq!(4, middle.lines.len());
+    assert!((area(&middle) - 6.).abs() < 0.0001);
+
+    assert!((area(&cube.slice(&Plane(Vector(0., 0., 1.), 1.))) - 6.).abs() < 0.0001);
+    assert_eq!(0, cube.slice(&Plane(Vector(0., 0., 1.), 0.)).lines.len());
+    assert_eq!(0, cube.slice(&Plane(Vector(0., 0., 1.), 2.)).lines.len());
+}
+
+/// Prism on a U shaped face, with its outline starting on one arm so the crossings of a cut
+/// through both arms do not come in pairs along it.
+fn u_prism() -> Csg {
+    let outline = [
+        (0., 2.),
+        (0., 0.),
+        (3., 0.),
+        (3., 2.),
+        (2., 2.),
+        (2., 1.),
+        (1., 1.),
+        (1., 2.),
+    ];
+    let face = |points: Vec<Vector>, normal: Vector| {
+        Polygon::new(points.into_iter().map(|p| Vertex::new(p, normal)).collect())
+    };
+
+    // Both faces start on a convex corner, where their planes are taken from
+    let top = outline.iter().map(|&(x, y)| Vector(x, y, 1.)).collect();
+    let mut bottom: Vec<Vector> = outline
+        .iter()
+        .rev()
+        .map(|&(x, y)| Vector(x, y, 0.))
+        .collect();
+    bottom.rotate_left(4);
+    let mut polygons = vec![
+        face(top, Vector(0., 0., 1.)),
+        face(bottom, Vector(0., 0., -1.)),
+    ];
+    for i in 0..outline.len() {
+        let (x0, y0) = outline[i];
+        let (x1, y1) = outline[(i + 1) % outline.len()];
+        let normal = Vector(y1 - y0, x0 - x1, 0.).normalize();
+        polygons.push(face(
+            vec![
+                Vector(x0, y0, 0.),
+                Vector(x1, y1, 0.),
+                Vector(x1, y1, 1.),
+                Vector(x0, y0, 1.),
+            ],
+            normal,
+        ));
+    }
+    Csg::from_polygons(polygons)
+}
+
+#[test]
+fn slice_concave_faces() {
+    // Two unit squares, one through each arm
+    let arms = u_prism().slice(&Plane(Vector(0., 1., 0.), 1.5));
+    assert_eq!(2, arms.to_regions().len());
+    assert!((area(&arms) - 2.).abs() < 0.0001);
+
+    let base = u_prism().slice(&Plane(Vector(0., 1., 0.), 0.5));
+    assert_eq!(1, base.to_regions().len());
+    assert!((area(&base) - 3.).abs() < 0.0001);
+}
+
+#[test]
+fn slice_torus() {
+    let torus = Csg::torus(2., 0.5, 32, 16);
+
+    // Flat cut gives a ring
+    let ring = torus.slice(&Plane(Vector(0., 0., 1.), 0.));
+    let regions = ring.to_regions();
+    assert_eq!(1, regions.len());
+    assert_eq!(1, regions[0].holes.len());
+    let expected = ::UNIT_PI * (2.5 * 2.5 - 1.5 * 1.5);
+    assert!((area(&ring) - expected).abs() < expected * 0.02);
+
+    // Upright cut through the axis gives two discs
+    let discs = torus.slice(&Plane(Vector(0., 1., 0.), 0.));
+    assert_eq!(2, discs.to_regions().len());
+    let expected = 2. * ::UNIT_PI * 0.25;
+    assert!((area(&discs) - expected).abs() < expected * 0.05);
+}
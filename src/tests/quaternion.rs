@@ -0,0 +1,60 @@
+use super::{assert_close, assert_vector_close};
+use dim3::{Csg, Matrix4, Quaternion, Vector};
+
+#[test]
+fn axis_angle_matches_matrix() {
+    let axis = Vector(1., 2., 3.);
+    let q = Quaternion::from_axis_angle(axis, 40.);
+    let m = Matrix4::rotation(axis, 40.);
+
+    let v = Vector(3., -1., 2.);
+    assert_vector_close(m.transform_point(v), q.rotate_vector(v));
+    assert_vector_close(m.transform_point(v), q.to_matrix().transform_point(v));
+    assert_vector_close(v.rotate(axis.normalize(), 40.), q.rotate_vector(v));
+}
+
+#[test]
+fn euler_order() {
+    let q = Quaternion::from_euler(90., 90., 0.);
+    let v = Vector(0., 1., 0.);
+
+    let expected = v
+        .rotate(Vector(1., 0., 0.), 90.)
+        .rotate(Vector(0., 1., 0.), 90.);
+    assert_vector_close(expected, q.rotate_vector(v));
+}
+
+#[test]
+fn slerp_halfway() {
+    let axis = Vector(0., 0., 1.);
+    let a = Quaternion::identity();
+    let b = Quaternion::from_axis_angle(axis, 120.);
+
+    let v = Vector(1., 0., 0.);
+    assert_vector_close(v, a.slerp(b, 0.).rotate_vector(v));
+    assert_vector_close(b.rotate_vector(v), a.slerp(b, 1.).rotate_vector(v));
+    assert_vector_close(v.rotate(axis, 60.), a.slerp(b, 0.5).rotate_vector(v));
+
+    // Same rotation with the opposite sign still goes the short way
+    let b_neg = Quaternion(-b.0, -b.1);
+    assert_vector_close(v.rotate(axis, 60.), a.slerp(b_neg, 0.5).rotate_vector(v));
+    assert_close(1., a.slerp(b, 0.3).length(), 0.0001);
+}
+
+#[test]
+fn rotate_quat_composes() {
+    let qa = Quaternion::from_axis_angle(Vector(0., 0., 1.), 30.);
+    let qb = Quaternion::from_axis_angle(Vector(1., 1., 0.), 45.);
+
+    let once = Csg::cube(Vector(1., 2., 3.), false).rotate_quat(qb * qa);
+    let twice = Csg::cube(Vector(1., 2., 3.), false)
+        .rotate(Vector(0., 0., 1.), 30.)
+        .rotate(Vector(1., 1., 0.).normalize(), 45.);
+
+    for (a, b) in once.polygons.iter().zip(twice.polygons.iter()) {
+        assert_vector_close(b.plane.0, a.plane.0);
+        for (va, vb) in a.vertices.iter().zip(b.vertices.iter()) {
+            assert_vector_close(vb.position, va.position);
+        }
+    }
+}
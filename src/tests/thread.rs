@@ -0,0 +1,47 @@
+use super::{assert_close, volume, BoundBox};
+use dim3::{Csg, ThreadProfile};
+use {Unit, UNIT_PI};
+
+/// Smallest and largest distance from the Z axis.
+fn radial_extent(csg: &Csg) -> (Unit, Unit) {
+    csg.polygons
+        .iter()
+        .flat_map(|p| p.vertices.iter())
+        .filter(|v| v.position.0 != 0. || v.position.1 != 0.)
+        .fold((Unit::MAX, 0.), |(lo, hi), v| {
+            let r = (v.position.0 * v.position.0 + v.position.1 * v.position.1).sqrt();
+            (lo.min(r), hi.max(r))
+        })
+}
+
+#[test]
+fn iso_thread() {
+    let thread = Csg::thread(1., 6., 4.9, 5., ThreadProfile::Iso, 32);
+
+    let (lo, hi) = radial_extent(&thread);
+    assert_close(2.45, lo, 0.0001);
+    assert_close(3., hi, 0.0001);
+
+    let bb = BoundBox::from_csg(&thread);
+    let (d_min, d_max) = bb.get_min_max_discreet(10.);
+    assert_eq!((0, 50), (d_min.2, d_max.2));
+
+    // Mean tooth height of the ISO profile is 7/16
+    let mean_radius: Unit = 2.45 + 0.55 * 7. / 16.;
+    let expected = UNIT_PI * mean_radius * mean_radius * 5.;
+    assert_close(expected, volume(&thread), expected * 0.02);
+}
+
+#[test]
+fn internal_thread_cuts_through() {
+    let tool = Csg::internal_thread(1., 6., 4.9, 5., ThreadProfile::Square, 16, 0.2);
+
+    let (lo, hi) = radial_extent(&tool);
+    assert_close(2.55, lo, 0.0001);
+    assert_close(3.1, hi, 0.0001);
+
+    let bb = BoundBox::from_csg(&tool);
+    let (d_min, d_max) = bb.get_min_max_discreet(10.);
+    assert_eq!((-10, 60), (d_min.2, d_max.2));
+    assert!(volume(&tool) > 0.);
+}